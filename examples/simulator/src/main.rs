@@ -4,23 +4,22 @@ use embedded_graphics::mono_font::{MonoTextStyle, ascii::FONT_7X13};
 use embedded_graphics::{prelude::*, primitives::*, text::*};
 
 use epd_yrd0750ryf665f60::{
+    mock::MockPanel,
     prelude::*,
     yrd0750ryf665f60::{Display7in5, Epd7in5},
 };
 
-mod mock;
-use mock::*;
-
 #[embassy_executor::main]
 async fn main_task(_spawner: Spawner) {
     println!("EPD 7.5英寸模拟器测试");
 
     // 创建模拟的硬件引脚
-    let mut spi = MockSpi;
-    let busy = MockInputPin;
-    let dc = MockOutputPin;
-    let rst = MockOutputPin;
-    let mut delay = MockDelay;
+    let panel = MockPanel::default();
+    let mut spi = panel.spi();
+    let busy = panel.busy();
+    let dc = panel.dc();
+    let rst = panel.rst();
+    let mut delay = panel.delay();
 
     // 初始化EPD
     println!("正在初始化EPD...");
@@ -72,6 +71,9 @@ async fn main_task(_spawner: Spawner) {
         .expect("更新显示失败");
 
     println!("测试完成！模拟器窗口已打开");
+    for violation in panel.violations() {
+        println!("协议违规：{:?}", violation);
+    }
 
     // 保持程序运行
     loop {
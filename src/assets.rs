@@ -0,0 +1,166 @@
+//! 构建期图像转换
+//!
+//! 在 `build.rs` 中将 PNG/BMP 图像转换为打包的四色数据，
+//! 再通过 [`include_quad_image!`](crate::include_quad_image) 嵌入固件：
+//!
+//! ```ignore
+//! // build.rs
+//! use epd_yrd0750ryf665f60::{assets, dither::DitherMode};
+//!
+//! fn main() {
+//!     assets::include_image("assets/logo.png", "logo", DitherMode::FloydSteinberg).unwrap();
+//! }
+//!
+//! // main.rs
+//! use epd_yrd0750ryf665f60::{image::QuadImage, include_quad_image};
+//!
+//! static LOGO: QuadImage<'static> = include_quad_image!("logo");
+//! ```
+
+extern crate std;
+
+use std::io;
+use std::path::Path;
+use std::string::ToString;
+use std::vec::Vec;
+use std::{format, println, vec};
+
+use embedded_graphics_core::pixelcolor::Rgb888;
+
+use crate::color::QuadColor;
+use crate::decode::{Bmp, Png};
+use crate::dither::{DitherMode, Ditherer};
+use crate::image::QuadImage;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+const BMP_SIGNATURE: &[u8] = b"BM";
+
+/// 解码后的 RGB 图像，透明部分已混合到白色背景上
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RgbImage {
+    /// 宽度（像素）
+    pub width: u32,
+    /// 高度（像素）
+    pub height: u32,
+    /// 按行存储的像素
+    pub pixels: Vec<Rgb888>,
+}
+
+/// 打包的 2 bpp 四色图像，格式与 [`QuadImage`] 相同
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PackedImage {
+    /// 宽度（像素）
+    pub width: u32,
+    /// 高度（像素）
+    pub height: u32,
+    /// 打包数据
+    pub data: Vec<u8>,
+}
+
+impl RgbImage {
+    /// 从 PNG 或 BMP 文件内容解码，格式由文件签名判断
+    pub fn decode(bytes: &[u8]) -> io::Result<Self> {
+        if bytes.starts_with(PNG_SIGNATURE) {
+            decode_png(bytes)
+        } else if bytes.starts_with(BMP_SIGNATURE) {
+            decode_bmp(bytes)
+        } else {
+            Err(invalid_data("不支持的图像格式，仅支持 PNG 和 BMP"))
+        }
+    }
+
+    /// 读取并解码图像文件
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::decode(&std::fs::read(path)?)
+    }
+
+    /// 量化为四色并打包
+    pub fn pack(&self, dither: DitherMode) -> PackedImage {
+        let width = self.width as usize;
+        let row_bytes = width.div_ceil(4);
+        let mut data = vec![QuadColor::default_color_byte(); row_bytes * self.height as usize];
+        let mut errors = vec![[0; 3]; width * 2];
+        let mut ditherer = Ditherer::new(dither, &mut errors);
+
+        for (row, out) in self
+            .pixels
+            .chunks(width.max(1))
+            .zip(data.chunks_mut(row_bytes.max(1)))
+        {
+            ditherer.dither_row(row.iter().copied(), |x, color| {
+                let shift = 6 - (x % 4) * 2;
+                out[x / 4] = out[x / 4] & !(0x03 << shift) | color.bits() << shift;
+            });
+        }
+
+        PackedImage {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+}
+
+impl PackedImage {
+    /// 以 [`QuadImage`] 形式访问
+    pub fn as_image(&self) -> QuadImage<'_> {
+        QuadImage::new(&self.data, self.width)
+    }
+
+    /// 序列化为带文件头的嵌入格式，见 [`QuadImage::from_embedded`]
+    pub fn to_bytes(&self) -> io::Result<Vec<u8>> {
+        let width = u16::try_from(self.width).map_err(|_| invalid_data("图像宽度超过 65535"))?;
+        let height = u16::try_from(self.height).map_err(|_| invalid_data("图像高度超过 65535"))?;
+        let mut bytes = Vec::with_capacity(QuadImage::HEADER_LEN + self.data.len());
+        bytes.extend_from_slice(&width.to_le_bytes());
+        bytes.extend_from_slice(&height.to_le_bytes());
+        bytes.extend_from_slice(&self.data);
+        Ok(bytes)
+    }
+}
+
+/// 在 `build.rs` 中转换图像并写入 `$OUT_DIR/<name>.qimg`
+///
+/// 同时为源图像输出 `cargo:rerun-if-changed`，图像修改后会自动重新转换。
+/// `name` 需要与 [`include_quad_image!`](crate::include_quad_image) 的参数一致
+pub fn include_image(path: impl AsRef<Path>, name: &str, dither: DitherMode) -> io::Result<()> {
+    let path = path.as_ref();
+    println!("cargo:rerun-if-changed={}", path.display());
+
+    let out_dir = std::env::var_os("OUT_DIR").ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "OUT_DIR 未设置，需在 build.rs 中调用",
+        )
+    })?;
+    let bytes = RgbImage::open(path)?.pack(dither).to_bytes()?;
+    std::fs::write(Path::new(&out_dir).join(format!("{name}.qimg")), bytes)
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn decode_png(bytes: &[u8]) -> io::Result<RgbImage> {
+    let mut png = Png::new(bytes).map_err(io::Error::other)?;
+    let size = png.size();
+    let pixels = png.by_ref().collect();
+    if let Some(e) = png.error() {
+        return Err(invalid_data(&e.to_string()));
+    }
+    Ok(RgbImage {
+        width: size.width,
+        height: size.height,
+        pixels,
+    })
+}
+
+fn decode_bmp(bytes: &[u8]) -> io::Result<RgbImage> {
+    let bmp = Bmp::new(bytes).map_err(|e| invalid_data(&e.to_string()))?;
+    let size = bmp.size();
+    Ok(RgbImage {
+        width: size.width,
+        height: size.height,
+        pixels: bmp.pixels().collect(),
+    })
+}
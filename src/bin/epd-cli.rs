@@ -0,0 +1,269 @@
+//! 在树莓派等 Linux 主机上直接驱动 YRD0750RYF665F60 面板的命令行工具
+//!
+//! 用法：`epd-cli [选项] <init|clear|show <file>|compress <image> <out>|sleep|info>`
+
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::pin;
+use std::process::ExitCode;
+use std::task::{Context, Poll, Waker};
+
+use embedded_graphics_core::pixelcolor::{Rgb888, RgbColor};
+use embedded_graphics_core::prelude::*;
+use embedded_hal::digital::InputPin;
+use epd_yrd0750ryf665f60::TransferConfig;
+use epd_yrd0750ryf665f60::assets::RgbImage;
+use epd_yrd0750ryf665f60::dither::{DitherMode, Ditherer};
+use epd_yrd0750ryf665f60::graphics::BUFFER_MAGIC;
+use epd_yrd0750ryf665f60::linux::LinuxConfig;
+use epd_yrd0750ryf665f60::prelude::*;
+use epd_yrd0750ryf665f60::rle;
+use epd_yrd0750ryf665f60::yrd0750ryf665f60::{Display7in5, Epd7in5, HEIGHT, PANEL_ID, WIDTH};
+
+const USAGE: &str = "\
+用法: epd-cli [选项] <命令>
+
+命令:
+  init               复位并初始化面板
+  clear              将面板清为白色
+  show <file>        显示 PNG/BMP 图像（居中，超出部分裁剪）、缓冲区文件或 RLE 压缩帧
+  compress <image> <out>
+                     将图像转换为 RLE 压缩帧，不访问硬件
+  sleep              关闭电源并进入深度睡眠
+  info               打印面板与连接信息
+
+选项:
+  --spi <path>       spidev 设备（默认 /dev/spidev0.0）
+  --speed <hz>       SPI 时钟频率（默认 4000000）
+  --gpiochip <path>  GPIO 字符设备（默认 /dev/gpiochip0）
+  --busy <line>      BUSY 引脚线号（默认 24）
+  --dc <line>        DC 引脚线号（默认 25）
+  --rst <line>       RST 引脚线号（默认 17）
+  --no-dither        按最近颜色映射，不做误差扩散抖动
+";
+
+enum Action {
+    Init,
+    Clear,
+    Show(PathBuf),
+    Compress(PathBuf, PathBuf),
+    Sleep,
+    Info,
+}
+
+struct Args {
+    config: LinuxConfig,
+    dither: DitherMode,
+    action: Action,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut config = LinuxConfig::default();
+    let mut dither = DitherMode::FloydSteinberg;
+    let mut action = None;
+    let mut args = std::env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{} 缺少参数", name));
+        match arg.as_str() {
+            "--spi" => config.spi_path = value(&arg)?.into(),
+            "--gpiochip" => config.gpiochip = value(&arg)?.into(),
+            "--speed" => config.spi_hz = parse_number(&arg, value(&arg)?)?,
+            "--busy" => config.busy = parse_number(&arg, value(&arg)?)?,
+            "--dc" => config.dc = parse_number(&arg, value(&arg)?)?,
+            "--rst" => config.rst = parse_number(&arg, value(&arg)?)?,
+            "--no-dither" => dither = DitherMode::None,
+            "-h" | "--help" => return Err(String::new()),
+            "init" => action = Some(Action::Init),
+            "clear" => action = Some(Action::Clear),
+            "show" => action = Some(Action::Show(value(&arg)?.into())),
+            "compress" => {
+                let input = value(&arg)?.into();
+                action = Some(Action::Compress(input, value(&arg)?.into()));
+            }
+            "sleep" => action = Some(Action::Sleep),
+            "info" => action = Some(Action::Info),
+            _ => return Err(format!("未知参数: {}", arg)),
+        }
+    }
+
+    Ok(Args {
+        config,
+        dither,
+        action: action.ok_or("缺少命令")?,
+    })
+}
+
+fn parse_number(name: &str, value: String) -> Result<u32, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} 的参数无效: {}", name, value))
+}
+
+/// 执行不会挂起的 future（所有 Linux 外设操作都是阻塞的）
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(message) => {
+            if !message.is_empty() {
+                eprintln!("{}\n", message);
+            }
+            eprint!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("错误: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Args) -> Result<(), String> {
+    // 在打开硬件之前先解码图像，避免文件错误时面板被复位
+    let frame = match &args.action {
+        Action::Show(path) => Some(load_frame(path, args.dither)?),
+        Action::Compress(input, output) => {
+            let display = load_image(input, args.dither)?;
+            let mut compressed = Vec::new();
+            rle::encode(display.buffer(), |bytes| {
+                compressed.extend_from_slice(bytes)
+            });
+            std::fs::write(output, &compressed)
+                .map_err(|e| format!("写入 {} 失败: {}", output.display(), e))?;
+            println!(
+                "{} 字节压缩为 {} 字节",
+                display.buffer().len(),
+                compressed.len()
+            );
+            return Ok(());
+        }
+        _ => None,
+    };
+
+    let mut peripherals = args
+        .config
+        .open()
+        .map_err(|e| format!("打开外设失败: {}", e))?;
+
+    if let Action::Info = args.action {
+        let config = &args.config;
+        println!("面板:     YRD0750RYF665F60");
+        println!("分辨率:   {}x{}", WIDTH, HEIGHT);
+        println!("缓冲区:   {} 字节", Display7in5::default().buffer().len());
+        println!(
+            "SPI:      {} @ {} Hz",
+            config.spi_path.display(),
+            config.spi_hz
+        );
+        println!("GPIO:     {}", config.gpiochip.display());
+        println!(
+            "引脚:     BUSY={} DC={} RST={}",
+            config.busy, config.dc, config.rst
+        );
+        let busy = peripherals
+            .busy
+            .is_low()
+            .map_err(|e| format!("读取 BUSY 失败: {}", e))?;
+        println!("状态:     {}", if busy { "忙" } else { "空闲" });
+        return Ok(());
+    }
+
+    let spi = &mut peripherals.spi;
+    let delay = &mut peripherals.delay;
+    block_on(async {
+        let mut epd = Epd7in5::new(
+            spi,
+            peripherals.busy,
+            peripherals.dc,
+            peripherals.rst,
+            delay,
+        )
+        .await?;
+        epd.set_transfer_config(TransferConfig::SPIDEV);
+
+        match args.action {
+            Action::Init => epd.wake_up(spi, delay).await,
+            Action::Clear => {
+                epd.wake_up(spi, delay).await?;
+                epd.clear_frame(spi).await?;
+                epd.display_frame(spi).await
+            }
+            Action::Show(_) => {
+                let frame = frame.as_ref().expect("图像已在打开外设前解码");
+                epd.wake_up(spi, delay).await?;
+                epd.update_and_display_frame(spi, frame).await
+            }
+            Action::Sleep => epd.sleep(spi).await,
+            Action::Info | Action::Compress(..) => Ok(()),
+        }
+    })
+    .map_err(|e| format!("SPI 通信失败: {}", e))
+}
+
+/// 读取要显示的帧：缓冲区文件直接载入，RLE 压缩帧解压，其他文件按图像处理
+fn load_frame(path: &PathBuf, dither: DitherMode) -> Result<Vec<u8>, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("读取 {} 失败: {}", path.display(), e))?;
+    if bytes.starts_with(&BUFFER_MAGIC) {
+        let mut display = Box::new(Display7in5::default());
+        display
+            .load_bytes(&bytes, PANEL_ID)
+            .map_err(|e| format!("载入 {} 失败: {:?}", path.display(), e))?;
+        return Ok(display.buffer().to_vec());
+    }
+    if !bytes.starts_with(&rle::MAGIC) {
+        return Ok(load_image(path, dither)?.buffer().to_vec());
+    }
+
+    let frame: Vec<u8> = rle::Decoder::new(&bytes)
+        .map_err(|e| format!("解压 {} 失败: {:?}", path.display(), e))?
+        .collect();
+    let expected = Display7in5::default().buffer().len();
+    if frame.len() != expected {
+        return Err(format!(
+            "压缩帧长度为 {} 字节，面板需要 {} 字节",
+            frame.len(),
+            expected
+        ));
+    }
+    Ok(frame)
+}
+
+/// 解码图像，居中放置到面板缓冲区并量化为四色
+fn load_image(path: &PathBuf, dither: DitherMode) -> Result<Box<Display7in5>, String> {
+    let image = RgbImage::open(path).map_err(|e| format!("读取 {} 失败: {}", path.display(), e))?;
+    let offset_x = (WIDTH as i64 - image.width as i64) / 2;
+    let offset_y = (HEIGHT as i64 - image.height as i64) / 2;
+
+    let mut display = Box::new(Display7in5::default());
+    let mut errors = vec![[0; 3]; WIDTH as usize * 2];
+    let mut ditherer = Ditherer::new(dither, &mut errors);
+    for y in 0..HEIGHT as i64 {
+        // 超出图像的部分以白色填充
+        let row = (0..WIDTH as i64).map(|x| {
+            let (ix, iy) = (x - offset_x, y - offset_y);
+            if ix < 0 || iy < 0 || ix >= image.width as i64 || iy >= image.height as i64 {
+                Rgb888::WHITE
+            } else {
+                image.pixels[(iy * image.width as i64 + ix) as usize]
+            }
+        });
+        ditherer.dither_row(row, |x, color| {
+            display.set_pixel(Pixel(Point::new(x as i32, y as i32), color));
+        });
+    }
+    Ok(display)
+}
@@ -19,18 +19,61 @@ pub enum QuadColor {
     Yellow,
 }
 
+/// 黑/白/红三色，用于双平面面板
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TriColor {
+    /// 黑色
+    Black,
+    /// 白色
+    #[default]
+    White,
+    /// 彩色（红色或黄色，取决于面板）
+    Chromatic,
+}
+
+/// ACeP 七色，编码即面板的 4 位颜色值
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SevenColor {
+    /// 黑色
+    Black,
+    /// 白色
+    #[default]
+    White,
+    /// 绿色
+    Green,
+    /// 蓝色
+    Blue,
+    /// 红色
+    Red,
+    /// 黄色
+    Yellow,
+    /// 橙色
+    Orange,
+}
+
 /// 颜色类型 trait，用于 `Display`
 pub trait ColorType {
     /// 每个像素占用的位数
     const BITS_PER_PIXEL: usize;
 
+    /// 缓冲区的默认填充字节，即全部像素为背景色（白色）
+    const DEFAULT_COLOR_BYTE: u8;
+
+    /// 缓冲区平面数；双平面颜色依次存放黑白平面和彩色平面，
+    /// `BITS_PER_PIXEL` 为每个平面的位数
+    const BUFFER_COUNT: usize = 1;
+
+    /// 彩色平面的默认填充字节，仅双平面颜色使用
+    const DEFAULT_CHROMATIC_BYTE: u8 = 0x00;
+
     /// 返回设置像素颜色所需的数据
     ///
     /// * pos: 像素在行中的位置，用于确定需要设置哪些像素
     ///
     /// 返回值：
     /// * .0: 用于从字节中排除此像素的掩码（如 BiColor 中的 0x7F）
-    /// * .1: 用于在字节中设置颜色的位（如 BiColor 中的 0x80）
+    /// * .1: 用于在字节中设置颜色的位（如 BiColor 中的 0x80），
+    ///   双平面颜色的高 8 位为彩色平面的位
     fn bitmask(&self, pos: u32) -> (u8, u16);
 
     /// 从位转换为颜色
@@ -48,10 +91,29 @@ impl QuadColor {
     pub const fn default_color_byte() -> u8 {
         0x55 // 0b01010101
     }
+
+    /// 返回颜色在缓冲区中的 2 位编码
+    pub const fn bits(self) -> u8 {
+        match self {
+            QuadColor::Black => 0b00,
+            QuadColor::White => 0b01,
+            QuadColor::Yellow => 0b10,
+            QuadColor::Red => 0b11,
+        }
+    }
+
+    /// 返回与给定颜色最接近的墨水颜色
+    ///
+    /// `From<Rgb888>` 只精确匹配黑、白、黄，照片等任意颜色应使用此函数
+    #[cfg(feature = "graphics")]
+    pub fn nearest(color: embedded_graphics_core::pixelcolor::Rgb888) -> Self {
+        crate::dither::nearest(color)
+    }
 }
 
 impl ColorType for QuadColor {
     const BITS_PER_PIXEL: usize = 2;
+    const DEFAULT_COLOR_BYTE: u8 = QuadColor::default_color_byte();
 
     fn bitmask(&self, pos: u32) -> (u8, u16) {
         let shift = if cfg!(feature = "simulator") {
@@ -60,13 +122,7 @@ impl ColorType for QuadColor {
             6 - (pos % 4) * 2
         };
         let mask = !(0x03 << shift);
-        let color_bits = match self {
-            QuadColor::Black => 0b00,
-            QuadColor::White => 0b01,
-            QuadColor::Yellow => 0b10,
-            QuadColor::Red => 0b11,
-        };
-        let value = (color_bits << shift) as u16;
+        let value = (self.bits() << shift) as u16;
 
         (mask, value)
     }
@@ -81,6 +137,221 @@ impl ColorType for QuadColor {
     }
 }
 
+/// 黑白面板：黑色（`On`）为 0，白色（`Off`）为 1
+#[cfg(feature = "graphics")]
+impl ColorType for BinaryColor {
+    const BITS_PER_PIXEL: usize = 1;
+    const DEFAULT_COLOR_BYTE: u8 = 0xFF;
+
+    fn bitmask(&self, pos: u32) -> (u8, u16) {
+        let shift = if cfg!(feature = "simulator") {
+            pos % 8
+        } else {
+            7 - pos % 8
+        };
+        let bit = match self {
+            BinaryColor::On => 0,
+            BinaryColor::Off => 1,
+        };
+
+        (!(1 << shift), bit << shift)
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        if bits & 0x01 == 0 {
+            BinaryColor::On
+        } else {
+            BinaryColor::Off
+        }
+    }
+}
+
+/// 黑白平面：黑色为 0，白色和彩色为 1；彩色平面：彩色为 1
+impl ColorType for TriColor {
+    const BITS_PER_PIXEL: usize = 1;
+    const DEFAULT_COLOR_BYTE: u8 = 0xFF;
+    const BUFFER_COUNT: usize = 2;
+    const DEFAULT_CHROMATIC_BYTE: u8 = 0x00;
+
+    fn bitmask(&self, pos: u32) -> (u8, u16) {
+        let shift = if cfg!(feature = "simulator") {
+            pos % 8
+        } else {
+            7 - pos % 8
+        };
+        let bit = 1u16 << shift;
+        let value = match self {
+            TriColor::Black => 0,
+            TriColor::White => bit,
+            TriColor::Chromatic => bit << 8 | bit,
+        };
+
+        (!(1 << shift), value)
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        match bits & 0x03 {
+            0b00 => TriColor::Black,
+            0b01 => TriColor::White,
+            _ => TriColor::Chromatic,
+        }
+    }
+}
+
+impl SevenColor {
+    /// 全部颜色，按编码排列
+    pub const ALL: [SevenColor; 7] = [
+        SevenColor::Black,
+        SevenColor::White,
+        SevenColor::Green,
+        SevenColor::Blue,
+        SevenColor::Red,
+        SevenColor::Yellow,
+        SevenColor::Orange,
+    ];
+
+    /// 返回颜色在缓冲区中的 4 位编码
+    pub const fn bits(self) -> u8 {
+        self as u8
+    }
+
+    /// 颜色的近似 RGB 值
+    #[cfg(feature = "graphics")]
+    const fn rgb(self) -> [u8; 3] {
+        match self {
+            SevenColor::Black => [0, 0, 0],
+            SevenColor::White => [255, 255, 255],
+            SevenColor::Green => [0, 255, 0],
+            SevenColor::Blue => [0, 0, 255],
+            SevenColor::Red => [255, 0, 0],
+            SevenColor::Yellow => [255, 255, 0],
+            SevenColor::Orange => [255, 128, 0],
+        }
+    }
+}
+
+impl ColorType for SevenColor {
+    const BITS_PER_PIXEL: usize = 4;
+    const DEFAULT_COLOR_BYTE: u8 = 0x11;
+
+    fn bitmask(&self, pos: u32) -> (u8, u16) {
+        let shift = if cfg!(feature = "simulator") {
+            (pos % 2) * 4
+        } else {
+            4 - (pos % 2) * 4
+        };
+        let mask = !(0x0F << shift);
+        let value = (self.bits() << shift) as u16;
+
+        (mask, value)
+    }
+
+    /// 未定义的编码（如面板的 0x7 清洁色）按白色处理
+    fn from_bits(bits: u8) -> Self {
+        SevenColor::ALL
+            .get((bits & 0x0F) as usize)
+            .copied()
+            .unwrap_or(SevenColor::White)
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl PixelColor for SevenColor {
+    type Raw = embedded_graphics_core::pixelcolor::raw::RawU4;
+}
+
+#[cfg(feature = "graphics")]
+impl From<embedded_graphics_core::pixelcolor::raw::RawU4> for SevenColor {
+    fn from(raw: embedded_graphics_core::pixelcolor::raw::RawU4) -> Self {
+        SevenColor::from_bits(embedded_graphics_core::prelude::RawData::into_inner(raw))
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl From<BinaryColor> for SevenColor {
+    fn from(b: BinaryColor) -> SevenColor {
+        match b {
+            BinaryColor::On => SevenColor::Black,
+            BinaryColor::Off => SevenColor::White,
+        }
+    }
+}
+
+/// 映射到 RGB 距离最近的颜色
+#[cfg(feature = "graphics")]
+impl From<embedded_graphics_core::pixelcolor::Rgb888> for SevenColor {
+    fn from(rgb: embedded_graphics_core::pixelcolor::Rgb888) -> Self {
+        use embedded_graphics_core::pixelcolor::RgbColor;
+        let target = [rgb.r(), rgb.g(), rgb.b()];
+        let distance = |color: &SevenColor| {
+            let rgb = color.rgb();
+            (0..3)
+                .map(|i| (rgb[i] as i32 - target[i] as i32).pow(2))
+                .sum::<i32>()
+        };
+        SevenColor::ALL
+            .into_iter()
+            .min_by_key(distance)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl From<SevenColor> for embedded_graphics_core::pixelcolor::Rgb888 {
+    fn from(seven_color: SevenColor) -> Self {
+        let [r, g, b] = match seven_color {
+            SevenColor::Black => [10, 10, 10],
+            SevenColor::White => [240, 240, 240],
+            SevenColor::Green => [40, 140, 60],
+            SevenColor::Blue => [40, 60, 160],
+            SevenColor::Red => [200, 50, 50],
+            SevenColor::Yellow => [240, 220, 60],
+            SevenColor::Orange => [230, 130, 40],
+        };
+        embedded_graphics_core::pixelcolor::Rgb888::new(r, g, b)
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl PixelColor for TriColor {
+    type Raw = ();
+}
+
+#[cfg(feature = "graphics")]
+impl From<BinaryColor> for TriColor {
+    fn from(b: BinaryColor) -> TriColor {
+        match b {
+            BinaryColor::On => TriColor::Black,
+            BinaryColor::Off => TriColor::White,
+        }
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl From<embedded_graphics_core::pixelcolor::Rgb888> for TriColor {
+    fn from(rgb: embedded_graphics_core::pixelcolor::Rgb888) -> Self {
+        use embedded_graphics_core::pixelcolor::RgbColor;
+        if rgb == RgbColor::BLACK {
+            TriColor::Black
+        } else if rgb == RgbColor::WHITE {
+            TriColor::White
+        } else {
+            TriColor::Chromatic
+        }
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl From<TriColor> for embedded_graphics_core::pixelcolor::Rgb888 {
+    fn from(tri_color: TriColor) -> Self {
+        match tri_color {
+            TriColor::Black => embedded_graphics_core::pixelcolor::Rgb888::new(10, 10, 10),
+            TriColor::White => embedded_graphics_core::pixelcolor::Rgb888::new(240, 240, 240),
+            TriColor::Chromatic => embedded_graphics_core::pixelcolor::Rgb888::new(200, 50, 50),
+        }
+    }
+}
+
 #[cfg(feature = "graphics")]
 impl PixelColor for QuadColor {
     type Raw = embedded_graphics_core::pixelcolor::raw::RawU2;
@@ -108,6 +379,7 @@ impl From<BinaryColor> for QuadColor {
     }
 }
 
+/// 精确匹配黑、白、黄，其他颜色均为红色；按最接近的颜色映射见 [`QuadColor::nearest`]
 #[cfg(feature = "graphics")]
 impl From<embedded_graphics_core::pixelcolor::Rgb888> for QuadColor {
     fn from(rgb: embedded_graphics_core::pixelcolor::Rgb888) -> Self {
@@ -124,6 +396,38 @@ impl From<embedded_graphics_core::pixelcolor::Rgb888> for QuadColor {
     }
 }
 
+/// 映射到最接近的墨水颜色，见 [`QuadColor::nearest`]
+#[cfg(feature = "graphics")]
+impl From<embedded_graphics_core::pixelcolor::Rgb565> for QuadColor {
+    fn from(color: embedded_graphics_core::pixelcolor::Rgb565) -> Self {
+        crate::dither::nearest(color.into())
+    }
+}
+
+/// 映射到最接近的墨水颜色，见 [`QuadColor::nearest`]
+#[cfg(feature = "graphics")]
+impl From<embedded_graphics_core::pixelcolor::Rgb555> for QuadColor {
+    fn from(color: embedded_graphics_core::pixelcolor::Rgb555) -> Self {
+        crate::dither::nearest(color.into())
+    }
+}
+
+/// 映射到最接近的墨水颜色，见 [`QuadColor::nearest`]
+#[cfg(feature = "graphics")]
+impl From<embedded_graphics_core::pixelcolor::Gray8> for QuadColor {
+    fn from(color: embedded_graphics_core::pixelcolor::Gray8) -> Self {
+        crate::dither::nearest(color.into())
+    }
+}
+
+/// 映射到最接近的墨水颜色，见 [`QuadColor::nearest`]
+#[cfg(feature = "graphics")]
+impl From<embedded_graphics_core::pixelcolor::Gray4> for QuadColor {
+    fn from(color: embedded_graphics_core::pixelcolor::Gray4) -> Self {
+        crate::dither::nearest(color.into())
+    }
+}
+
 #[cfg(feature = "graphics")]
 impl From<QuadColor> for embedded_graphics_core::pixelcolor::Rgb888 {
     fn from(quad_color: QuadColor) -> Self {
@@ -135,3 +439,72 @@ impl From<QuadColor> for embedded_graphics_core::pixelcolor::Rgb888 {
         }
     }
 }
+
+#[cfg(all(test, feature = "graphics"))]
+mod tests {
+    use super::*;
+    use embedded_graphics_core::pixelcolor::raw::RawU2;
+    use embedded_graphics_core::pixelcolor::{Gray8, GrayColor, Rgb565, Rgb888, RgbColor};
+
+    const QUAD_COLORS: [QuadColor; 4] = [
+        QuadColor::Black,
+        QuadColor::White,
+        QuadColor::Yellow,
+        QuadColor::Red,
+    ];
+
+    #[test]
+    fn quad_color_bits_round_trip() {
+        for color in QUAD_COLORS {
+            assert_eq!(QuadColor::from_bits(color.bits()), color);
+            assert_eq!(QuadColor::from(RawU2::new(color.bits())), color);
+        }
+        assert_eq!(QuadColor::from(BinaryColor::On), QuadColor::Black);
+        assert_eq!(QuadColor::from(BinaryColor::Off), QuadColor::White);
+    }
+
+    #[test]
+    fn rgb888_conversion_is_exact() {
+        assert_eq!(QuadColor::from(Rgb888::BLACK), QuadColor::Black);
+        assert_eq!(QuadColor::from(Rgb888::WHITE), QuadColor::White);
+        assert_eq!(QuadColor::from(Rgb888::YELLOW), QuadColor::Yellow);
+        assert_eq!(QuadColor::from(Rgb888::RED), QuadColor::Red);
+        // 非精确匹配的颜色均为红色
+        assert_eq!(QuadColor::from(Rgb888::new(20, 20, 20)), QuadColor::Red);
+        assert_eq!(QuadColor::from(Rgb888::new(250, 250, 250)), QuadColor::Red);
+    }
+
+    #[test]
+    fn nearest_picks_closest_ink() {
+        assert_eq!(
+            QuadColor::nearest(Rgb888::new(20, 20, 20)),
+            QuadColor::Black
+        );
+        assert_eq!(
+            QuadColor::nearest(Rgb888::new(250, 250, 250)),
+            QuadColor::White
+        );
+        assert_eq!(
+            QuadColor::nearest(Rgb888::new(230, 220, 40)),
+            QuadColor::Yellow
+        );
+        assert_eq!(QuadColor::nearest(Rgb888::new(200, 30, 40)), QuadColor::Red);
+        // 显示用的近似颜色映射回原颜色
+        for color in QUAD_COLORS {
+            assert_eq!(QuadColor::nearest(color.into()), color);
+        }
+    }
+
+    #[test]
+    fn other_color_types_use_nearest() {
+        assert_eq!(QuadColor::from(Rgb565::BLACK), QuadColor::Black);
+        assert_eq!(QuadColor::from(Rgb565::WHITE), QuadColor::White);
+        assert_eq!(QuadColor::from(Rgb565::YELLOW), QuadColor::Yellow);
+        assert_eq!(QuadColor::from(Rgb565::RED), QuadColor::Red);
+        assert_eq!(QuadColor::from(Rgb565::new(3, 3, 3)), QuadColor::Black);
+        assert_eq!(QuadColor::from(Gray8::BLACK), QuadColor::Black);
+        assert_eq!(QuadColor::from(Gray8::WHITE), QuadColor::White);
+        assert_eq!(QuadColor::from(Gray8::new(200)), QuadColor::White);
+        assert_eq!(QuadColor::from(Gray8::new(50)), QuadColor::Black);
+    }
+}
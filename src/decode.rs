@@ -0,0 +1,285 @@
+//! 逐行解码 BMP（`bmp` 特性）与 PNG（`png` 特性，需要 std）图像
+//!
+//! 解码器按行输出 RGB 像素，[`QuantizedRows`] 将其量化为四色后
+//! 绘制到 [`Display`]，或直接交给驱动的 `update_frame_from_rows` 流式传输，
+//! 无需 RGB 中间缓冲区：
+//!
+//! ```ignore
+//! let bmp = decode::Bmp::new(IMAGE)?;
+//! let mut errors = [[0; 3]; 2 * WIDTH as usize];
+//! let mut rows = QuantizedRows::new(bmp.size().width, bmp.pixels(), DitherMode::FloydSteinberg, &mut errors);
+//! epd.update_frame_from_rows(&mut spi, |_, row| rows.pack_next_row(row)).await?;
+//! ```
+
+#[cfg(feature = "png")]
+extern crate std;
+
+use core::fmt;
+#[cfg(feature = "png")]
+use std::vec;
+
+use embedded_graphics_core::pixelcolor::Rgb888;
+use embedded_graphics_core::prelude::*;
+
+use crate::color::{ColorType, QuadColor};
+use crate::dither::{DitherMode, Ditherer};
+use crate::graphics::Display;
+
+/// 解码错误
+#[derive(Debug)]
+pub enum DecodeError {
+    /// BMP 解析失败
+    #[cfg(feature = "bmp")]
+    Bmp(tinybmp::ParseError),
+    /// PNG 解码失败
+    #[cfg(feature = "png")]
+    Png(png::DecodingError),
+    /// 不支持的 PNG 颜色格式
+    #[cfg(feature = "png")]
+    UnsupportedPngFormat,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "bmp")]
+            DecodeError::Bmp(e) => write!(f, "解码 BMP 失败: {:?}", e),
+            #[cfg(feature = "png")]
+            DecodeError::Png(e) => write!(f, "解码 PNG 失败: {}", e),
+            #[cfg(feature = "png")]
+            DecodeError::UnsupportedPngFormat => f.write_str("不支持的 PNG 颜色格式"),
+        }
+    }
+}
+
+#[cfg(feature = "png")]
+impl std::error::Error for DecodeError {}
+
+/// BMP 图像，直接引用文件数据
+#[cfg(feature = "bmp")]
+#[derive(Clone, Copy, Debug)]
+pub struct Bmp<'a>(tinybmp::Bmp<'a, Rgb888>);
+
+#[cfg(feature = "bmp")]
+impl<'a> Bmp<'a> {
+    /// 解析文件头
+    pub fn new(bytes: &'a [u8]) -> Result<Self, DecodeError> {
+        tinybmp::Bmp::from_slice(bytes)
+            .map(Bmp)
+            .map_err(DecodeError::Bmp)
+    }
+
+    /// 图像尺寸
+    pub fn size(&self) -> Size {
+        self.0.size()
+    }
+
+    /// 从左上角开始逐行输出的像素
+    pub fn pixels(&self) -> impl Iterator<Item = Rgb888> + '_ {
+        self.0.pixels().map(|Pixel(_, color)| color)
+    }
+}
+
+/// PNG 图像，作为迭代器从左上角开始逐行输出像素
+///
+/// 非隔行图像每次只解码一行；隔行图像在创建时完整解码。
+/// 透明部分混合到白色背景上，数据损坏时迭代提前结束，可通过 [`Png::error`] 查询
+#[cfg(feature = "png")]
+pub struct Png<'a> {
+    reader: png::Reader<&'a [u8]>,
+    /// 隔行图像完整解码后的数据
+    frame: Option<std::vec::Vec<u8>>,
+    line: std::vec::Vec<u8>,
+    line_size: usize,
+    channels: usize,
+    width: u32,
+    height: u32,
+    x: u32,
+    y: u32,
+    error: Option<DecodeError>,
+}
+
+#[cfg(feature = "png")]
+impl<'a> Png<'a> {
+    /// 解析文件头
+    pub fn new(bytes: &'a [u8]) -> Result<Self, DecodeError> {
+        let mut decoder = png::Decoder::new(bytes);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info().map_err(DecodeError::Png)?;
+
+        let (color_type, _) = reader.output_color_type();
+        let channels = color_type.samples();
+        if !(1..=4).contains(&channels) {
+            return Err(DecodeError::UnsupportedPngFormat);
+        }
+        let info = reader.info();
+        let (width, height, interlaced) = (info.width, info.height, info.interlaced);
+        let line_size = reader.output_line_size(width);
+
+        let frame = if interlaced {
+            let mut frame = vec![0; reader.output_buffer_size()];
+            reader.next_frame(&mut frame).map_err(DecodeError::Png)?;
+            Some(frame)
+        } else {
+            None
+        };
+
+        Ok(Png {
+            reader,
+            frame,
+            line: vec![0; line_size],
+            line_size,
+            channels,
+            width,
+            height,
+            x: width,
+            y: 0,
+            error: None,
+        })
+    }
+
+    /// 图像尺寸
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+
+    /// 迭代过程中遇到的错误
+    pub fn error(&self) -> Option<&DecodeError> {
+        self.error.as_ref()
+    }
+
+    /// 读取下一行到 `line`
+    fn next_line(&mut self) -> bool {
+        if self.y >= self.height || self.error.is_some() {
+            return false;
+        }
+        let start = self.y as usize * self.line_size;
+        match &self.frame {
+            Some(frame) => self
+                .line
+                .copy_from_slice(&frame[start..start + self.line_size]),
+            None => match self.reader.next_row() {
+                Ok(Some(row)) => self.line.copy_from_slice(&row.data()[..self.line_size]),
+                Ok(None) => return false,
+                Err(e) => {
+                    self.error = Some(DecodeError::Png(e));
+                    return false;
+                }
+            },
+        }
+        self.y += 1;
+        self.x = 0;
+        true
+    }
+}
+
+#[cfg(feature = "png")]
+impl Iterator for Png<'_> {
+    type Item = Rgb888;
+
+    fn next(&mut self) -> Option<Rgb888> {
+        if self.x >= self.width && !self.next_line() {
+            return None;
+        }
+        let start = self.x as usize * self.channels;
+        self.x += 1;
+        let (rgb, alpha) = match self.line[start..start + self.channels] {
+            [l] => ([l; 3], 255),
+            [l, a] => ([l; 3], a),
+            [r, g, b] => ([r, g, b], 255),
+            [r, g, b, a] => ([r, g, b], a),
+            _ => unreachable!(),
+        };
+        let blend = |c: u8| ((c as u32 * alpha as u32 + 255 * (255 - alpha as u32)) / 255) as u8;
+        let [r, g, b] = rgb.map(blend);
+        Some(Rgb888::new(r, g, b))
+    }
+}
+
+/// 将逐行输出的 RGB 像素量化为四色
+///
+/// 每行处理 `width` 个像素；超出误差缓冲区宽度（`errors.len() / 2`）的像素被丢弃
+pub struct QuantizedRows<'e, I> {
+    pixels: I,
+    width: u32,
+    ditherer: Ditherer<'e>,
+}
+
+impl<'e, I: Iterator<Item = Rgb888>> QuantizedRows<'e, I> {
+    /// 创建量化器，`pixels` 从左上角开始逐行输出宽度为 `width` 的图像
+    pub fn new(width: u32, pixels: I, mode: DitherMode, errors: &'e mut [[i16; 3]]) -> Self {
+        QuantizedRows {
+            pixels,
+            width,
+            ditherer: Ditherer::new(mode, errors),
+        }
+    }
+
+    /// 量化下一行，`set(x, color)` 依次接收每个像素的结果；图像结束后不再调用
+    pub fn next_row(&mut self, mut set: impl FnMut(u32, QuadColor)) {
+        let mut row = self.pixels.by_ref().take(self.width as usize);
+        self.ditherer
+            .dither_row(&mut row, |x, color| set(x as u32, color));
+        row.for_each(drop);
+    }
+
+    /// 量化下一行并写入打包的行数据，可用作 `update_frame_from_rows` 的回调
+    ///
+    /// 超出 `row` 的像素被忽略，图像未覆盖的部分保持原值
+    pub fn pack_next_row(&mut self, row: &mut [u8]) {
+        self.next_row(|x, color| {
+            if let Some(byte) = row.get_mut(x as usize / 4) {
+                let (mask, bits) = color.bitmask(x);
+                *byte = *byte & mask | bits as u8;
+            }
+        });
+    }
+
+    /// 量化剩余的行并绘制到 `display`，图像左上角位于 `position`
+    pub fn draw<const WIDTH: u32, const HEIGHT: u32, const BYTECOUNT: usize>(
+        mut self,
+        display: &mut Display<WIDTH, HEIGHT, BYTECOUNT, QuadColor>,
+        position: Point,
+        height: u32,
+    ) {
+        for y in 0..height as i32 {
+            self.next_row(|x, color| {
+                display.set_pixel(Pixel(position + Point::new(x as i32, y), color));
+            });
+        }
+    }
+}
+
+/// 解码 BMP 并量化绘制到 `display`，图像左上角位于 `position`
+///
+/// `errors` 长度至少为图像宽度的两倍，见 [`Ditherer::new`]
+#[cfg(feature = "bmp")]
+pub fn draw_bmp<const WIDTH: u32, const HEIGHT: u32, const BYTECOUNT: usize>(
+    display: &mut Display<WIDTH, HEIGHT, BYTECOUNT, QuadColor>,
+    bytes: &[u8],
+    position: Point,
+    mode: DitherMode,
+    errors: &mut [[i16; 3]],
+) -> Result<(), DecodeError> {
+    let bmp = Bmp::new(bytes)?;
+    let size = bmp.size();
+    QuantizedRows::new(size.width, bmp.pixels(), mode, errors).draw(display, position, size.height);
+    Ok(())
+}
+
+/// 解码 PNG 并量化绘制到 `display`，图像左上角位于 `position`
+///
+/// `errors` 长度至少为图像宽度的两倍，见 [`Ditherer::new`]
+#[cfg(feature = "png")]
+pub fn draw_png<const WIDTH: u32, const HEIGHT: u32, const BYTECOUNT: usize>(
+    display: &mut Display<WIDTH, HEIGHT, BYTECOUNT, QuadColor>,
+    bytes: &[u8],
+    position: Point,
+    mode: DitherMode,
+    errors: &mut [[i16; 3]],
+) -> Result<(), DecodeError> {
+    let mut png = Png::new(bytes)?;
+    let size = png.size();
+    QuantizedRows::new(size.width, &mut png, mode, errors).draw(display, position, size.height);
+    png.error.map_or(Ok(()), Err)
+}
@@ -0,0 +1,109 @@
+//! 将全彩像素量化为四色
+//!
+//! [`Ditherer`] 逐行处理像素，误差缓冲区由调用者提供，因此也可在 `no_std` 下流式使用。
+
+use crate::color::QuadColor;
+use embedded_graphics_core::pixelcolor::{Rgb888, RgbColor};
+
+/// 四种墨水的理想 RGB 值
+const PALETTE: [(QuadColor, [i16; 3]); 4] = [
+    (QuadColor::Black, [0, 0, 0]),
+    (QuadColor::White, [255, 255, 255]),
+    (QuadColor::Red, [255, 0, 0]),
+    (QuadColor::Yellow, [255, 255, 0]),
+];
+
+/// 抖动方式
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum DitherMode {
+    /// 直接映射到最接近的颜色
+    None,
+    /// Floyd–Steinberg 误差扩散
+    #[default]
+    FloydSteinberg,
+}
+
+/// 返回与给定颜色最接近的墨水颜色
+pub fn nearest(color: Rgb888) -> QuadColor {
+    nearest_entry([color.r() as i16, color.g() as i16, color.b() as i16]).0
+}
+
+fn nearest_entry(rgb: [i16; 3]) -> (QuadColor, [i16; 3]) {
+    let distance = |target: &[i16; 3]| {
+        (0..3)
+            .map(|i| (rgb[i] as i32 - target[i] as i32).pow(2))
+            .sum::<i32>()
+    };
+    let mut best = PALETTE[0];
+    for entry in PALETTE.iter().skip(1) {
+        if distance(&entry.1) < distance(&best.1) {
+            best = *entry;
+        }
+    }
+    best
+}
+
+/// 逐行量化器
+pub struct Ditherer<'a> {
+    mode: DitherMode,
+    /// 当前行累积的误差
+    current: &'a mut [[i16; 3]],
+    /// 下一行累积的误差
+    next: &'a mut [[i16; 3]],
+}
+
+impl<'a> Ditherer<'a> {
+    /// 创建量化器
+    ///
+    /// `errors` 用于暂存扩散误差，长度至少为图像宽度的两倍；
+    /// 每行最多处理 `errors.len() / 2` 个像素
+    pub fn new(mode: DitherMode, errors: &'a mut [[i16; 3]]) -> Self {
+        errors.fill([0; 3]);
+        let width = errors.len() / 2;
+        let (current, next) = errors.split_at_mut(width);
+        Ditherer {
+            mode,
+            current,
+            next: &mut next[..width],
+        }
+    }
+
+    /// 量化一行像素，`set(x, color)` 依次接收每个像素的结果
+    pub fn dither_row<I, F>(&mut self, row: I, mut set: F)
+    where
+        I: IntoIterator<Item = Rgb888>,
+        F: FnMut(usize, QuadColor),
+    {
+        let width = self.current.len();
+        for (x, color) in row.into_iter().enumerate().take(width) {
+            let error = self.current[x];
+            let value = [
+                (color.r() as i16 + error[0]).clamp(0, 255),
+                (color.g() as i16 + error[1]).clamp(0, 255),
+                (color.b() as i16 + error[2]).clamp(0, 255),
+            ];
+            let (quad, target) = nearest_entry(value);
+            set(x, quad);
+
+            if self.mode == DitherMode::FloydSteinberg {
+                let error = [0, 1, 2].map(|i| value[i] - target[i]);
+                let spread = |errors: &mut [[i16; 3]], x: usize, weight: i16| {
+                    if let Some(pixel) = errors.get_mut(x) {
+                        for i in 0..3 {
+                            pixel[i] += error[i] * weight / 16;
+                        }
+                    }
+                };
+                spread(self.current, x + 1, 7);
+                if x > 0 {
+                    spread(self.next, x - 1, 3);
+                }
+                spread(self.next, x, 5);
+                spread(self.next, x + 1, 1);
+            }
+        }
+
+        core::mem::swap(&mut self.current, &mut self.next);
+        self.next.fill([0; 3]);
+    }
+}
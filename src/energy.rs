@@ -0,0 +1,196 @@
+//! 功耗统计
+//!
+//! 驱动启用统计后，复位延时改由统计器的 `DelayNs` 执行并计时，等待 BUSY 时按固定间隔轮询，
+//! 将复位、上电、刷新、断电期间的时间分别累计，再按各状态的电流估算电荷量与能量。
+//! 时间分辨率为 1 ms。各状态的电流需取自面板数据手册或实测：
+//!
+//! ```ignore
+//! const CURRENTS: PanelCurrents = PanelCurrents { /* 实测值 */ };
+//!
+//! epd.enable_energy_meter(embassy_time::Delay);
+//! epd.update_and_display_frame(&mut spi, display.buffer()).await?;
+//! let stats = epd.take_energy_stats().unwrap();
+//! let uj = stats.energy_uj(&CURRENTS, 3300);
+//! ```
+//!
+//! 两次操作之间的空闲时间驱动无法测量，可用驱动的 `add_elapsed_time` 计入当前状态。
+
+use embedded_hal_async::delay::DelayNs;
+
+/// 轮询 BUSY 引脚的间隔（微秒）
+const POLL_INTERVAL_US: u32 = 1_000;
+
+/// 面板的供电状态
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum PowerState {
+    /// 复位中
+    Reset,
+    /// 升压电路断电，控制器仍在工作
+    #[default]
+    Off,
+    /// 升压电路上电，未在刷新
+    On,
+    /// 刷新中
+    Refreshing,
+    /// 深度睡眠
+    DeepSleep,
+}
+
+/// 各状态下的电流（微安），应取自面板数据手册或实测
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PanelCurrents {
+    /// 复位期间
+    pub reset_ua: u32,
+    /// 升压电路断电
+    pub off_ua: u32,
+    /// 升压电路上电但未刷新
+    pub on_ua: u32,
+    /// 刷新期间
+    pub refresh_ua: u32,
+    /// 深度睡眠
+    pub deep_sleep_ua: u32,
+}
+
+/// 各状态累计的时间（微秒）
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct EnergyStats {
+    /// 复位
+    pub reset_us: u64,
+    /// 升压电路断电
+    pub off_us: u64,
+    /// 升压电路上电但未刷新
+    pub on_us: u64,
+    /// 刷新
+    pub refresh_us: u64,
+    /// 深度睡眠
+    pub deep_sleep_us: u64,
+}
+
+impl EnergyStats {
+    /// 统计的总时间（微秒）
+    pub fn total_us(&self) -> u64 {
+        self.reset_us + self.off_us + self.on_us + self.refresh_us + self.deep_sleep_us
+    }
+
+    /// 估算消耗的电荷量（微库仑），四舍五入
+    pub fn charge_uc(&self, currents: &PanelCurrents) -> u64 {
+        div_round(self.ua_us(currents), 1_000_000)
+    }
+
+    /// 按供电电压（毫伏）估算消耗的能量（微焦），四舍五入
+    pub fn energy_uj(&self, currents: &PanelCurrents, supply_mv: u32) -> u64 {
+        div_round(self.ua_us(currents) * supply_mv as u128, 1_000_000_000)
+    }
+
+    /// 各状态电流与时间乘积之和（微安·微秒）
+    fn ua_us(&self, currents: &PanelCurrents) -> u128 {
+        [
+            (self.reset_us, currents.reset_ua),
+            (self.off_us, currents.off_ua),
+            (self.on_us, currents.on_ua),
+            (self.refresh_us, currents.refresh_ua),
+            (self.deep_sleep_us, currents.deep_sleep_ua),
+        ]
+        .iter()
+        .map(|&(us, ua)| us as u128 * ua as u128)
+        .sum()
+    }
+
+    fn add(&mut self, state: PowerState, us: u64) {
+        let slot = match state {
+            PowerState::Reset => &mut self.reset_us,
+            PowerState::Off => &mut self.off_us,
+            PowerState::On => &mut self.on_us,
+            PowerState::Refreshing => &mut self.refresh_us,
+            PowerState::DeepSleep => &mut self.deep_sleep_us,
+        };
+        *slot += us;
+    }
+}
+
+fn div_round(value: u128, divisor: u128) -> u64 {
+    ((value + divisor / 2) / divisor) as u64
+}
+
+/// 驱动内部的状态计时器
+#[derive(Debug)]
+pub(crate) struct EnergyMeter<D> {
+    delay: D,
+    state: PowerState,
+    stats: EnergyStats,
+}
+
+impl<D: DelayNs> EnergyMeter<D> {
+    pub(crate) fn new(delay: D, state: PowerState) -> Self {
+        EnergyMeter {
+            delay,
+            state,
+            stats: EnergyStats::default(),
+        }
+    }
+
+    /// 切换到 `state`，之后的时间计入该状态
+    pub(crate) fn enter(&mut self, state: PowerState) {
+        self.state = state;
+    }
+
+    /// 将 `us` 微秒计入当前状态
+    pub(crate) fn add(&mut self, us: u64) {
+        self.stats.add(self.state, us);
+    }
+
+    /// 延时 `us` 微秒并计入当前状态
+    pub(crate) async fn delay_us(&mut self, us: u32) {
+        self.delay.delay_us(us).await;
+        self.add(us as u64);
+    }
+
+    /// 轮询 `is_busy` 直到空闲，等待时间计入当前状态
+    pub(crate) async fn wait_until_idle(&mut self, mut is_busy: impl FnMut() -> bool) {
+        while is_busy() {
+            self.delay.delay_us(POLL_INTERVAL_US).await;
+            self.add(POLL_INTERVAL_US as u64);
+        }
+    }
+
+    /// 截至当前的统计
+    pub(crate) fn stats(&self) -> EnergyStats {
+        self.stats
+    }
+
+    /// 取出截至当前的统计并清零
+    pub(crate) fn take(&mut self) -> EnergyStats {
+        core::mem::take(&mut self.stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURRENTS: PanelCurrents = PanelCurrents {
+        reset_ua: 0,
+        off_ua: 0,
+        on_ua: 0,
+        refresh_ua: 3,
+        deep_sleep_ua: 0,
+    };
+
+    #[test]
+    fn charge_and_energy_are_rounded() {
+        let stats = EnergyStats {
+            refresh_us: 500_000,
+            ..EnergyStats::default()
+        };
+        // 3 µA × 0.5 s = 1.5 µC
+        assert_eq!(stats.charge_uc(&CURRENTS), 2);
+        // 1.5 µC × 3.3 V = 4.95 µJ，按未取整的电荷量计算
+        assert_eq!(stats.energy_uj(&CURRENTS, 3300), 5);
+
+        let stats = EnergyStats {
+            refresh_us: 499_999,
+            ..EnergyStats::default()
+        };
+        assert_eq!(stats.charge_uc(&CURRENTS), 1);
+    }
+}
@@ -0,0 +1,449 @@
+//! GDEW075T7 黑白电子墨水屏驱动（UC8179 控制器）
+
+use core::marker::PhantomData;
+
+use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::{digital::Wait, spi::SpiDevice};
+
+use embedded_graphics_core::pixelcolor::BinaryColor;
+
+use crate::color::ColorType;
+use crate::interface::DisplayInterface;
+use crate::lut::{Lut, LutSource};
+use crate::refresh::{RefreshControl, RefreshMode};
+use crate::traits::{InternalWiAdditions, WaveshareDisplay};
+
+use crate::buffer_len;
+use crate::traits;
+
+#[cfg(feature = "simulator")]
+use embedded_graphics_core::prelude::*;
+
+#[cfg(feature = "simulator")]
+extern crate std;
+
+#[cfg(feature = "simulator")]
+use embedded_graphics_simulator::{OutputSettingsBuilder, SimulatorDisplay, Window};
+
+/// 7.5 英寸黑白 EPD 完整缓冲区
+pub type Display7in5 = crate::graphics::Display<
+    WIDTH,
+    HEIGHT,
+    { buffer_len(WIDTH as usize, HEIGHT as usize) },
+    BinaryColor,
+>;
+
+/// 面板编号，用于校验 `Display::to_bytes` 导出的缓冲区文件
+pub const PANEL_ID: u16 = 0x075B;
+
+/// 显示宽度
+pub const WIDTH: u32 = 800;
+/// 显示高度
+pub const HEIGHT: u32 = 480;
+
+/// 每行像素数据的字节数
+const ROW_BYTES: usize = WIDTH as usize / 8;
+
+/// 整帧数据的字节数
+const FRAME_BYTES: usize = ROW_BYTES * HEIGHT as usize;
+
+const IS_BUSY_LOW: bool = true;
+
+/// 面板设置：KW 模式，不含 REG 位
+const PANEL_SETTING: u8 = 0x1F;
+
+/// 面板设置中选择寄存器波形的 REG 位
+const PANEL_SETTING_REG: u8 = 0x20;
+
+/// EPD 命令
+#[derive(Copy, Clone)]
+#[allow(unused)]
+pub(crate) enum Command {
+    /// 面板设置
+    PanelSetting = 0x00,
+    /// 电源设置
+    PowerSetting = 0x01,
+    /// 关闭电源
+    PowerOff = 0x02,
+    /// 开启电源
+    PowerOn = 0x04,
+    /// 启动数据传输
+    BoosterSoftStart = 0x06,
+    /// 深度睡眠
+    DeepSleep = 0x07,
+    /// 开始传输数据（旧数据）
+    DataStartTransmission1 = 0x10,
+    /// 停止数据传输
+    DataStop = 0x11,
+    /// 显示刷新
+    DisplayRefresh = 0x12,
+    /// 开始传输数据（新数据）
+    DataStartTransmission2 = 0x13,
+    /// 双 SPI 模式
+    DualSpi = 0x15,
+    /// PLL 控制
+    PllControl = 0x30,
+    /// 温度传感器
+    TemperatureSensor = 0x40,
+    /// 温度校准
+    TemperatureCalibration = 0x41,
+    /// 温度传感器写入
+    TemperatureSensorWrite = 0x42,
+    /// 温度传感器读取
+    TemperatureSensorRead = 0x43,
+    /// 神秘命令 1
+    MisteryCommand1 = 0x4D,
+    /// VCOM 和数据间隔设置
+    VcomAndDataIntervalSetting = 0x50,
+    /// 低功耗检测
+    LowPowerDetection = 0x51,
+    /// TCON 设置
+    TconSetting = 0x60,
+    /// TCON 分辨率
+    TconResolution = 0x61,
+    /// SPI Flash 控制
+    SpiFlashControl = 0x65,
+    /// 版本
+    Revision = 0x70,
+    /// 自动测量 VCOM
+    AutoMeasurementVcom = 0x80,
+    /// 读取 VCOM 值
+    ReadVcomValue = 0x81,
+    /// VCOM DC 设置
+    VcmDcSetting = 0x82,
+    /// 部分窗口
+    PartialWindow = 0x83,
+    /// 编程模式
+    ProgramMode = 0x90,
+    /// 激活编程
+    ActiveProgram = 0x91,
+    /// 读取 MTP 数据
+    ReadMTPData = 0x92,
+    /// MTP 编程配置
+    MtpProgramConfig = 0xA2,
+    /// 级联设置
+    CascadeSetting = 0xE0,
+    /// 省电设置
+    PowerSavingSetting = 0xE3,
+    /// LVD 电压选择
+    LvdVoltageSelect = 0xE4,
+    /// 神秘命令 2
+    MisteryCommand2 = 0xE9,
+}
+
+impl traits::Command for Command {
+    fn address(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Epd7in5 (gdew075t7) 黑白驱动
+pub struct Epd7in5<SPI, BUSY, DC, RST, DELAY> {
+    interface: DisplayInterface<SPI, BUSY, DC, RST, DELAY>,
+    /// 是否已上传寄存器波形，复位后丢失
+    lut_loaded: bool,
+    /// 当前使用的波形来源
+    lut_source: LutSource,
+    refresh_mode: RefreshMode,
+    _color: PhantomData<BinaryColor>,
+    #[cfg(feature = "simulator")]
+    simulator_window: Option<core::cell::RefCell<Window>>,
+    #[cfg(feature = "simulator")]
+    simulator_display: SimulatorDisplay<BinaryColor>,
+}
+
+impl<SPI, BUSY, DC, RST, DELAY> Epd7in5<SPI, BUSY, DC, RST, DELAY>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin + Wait,
+    DC: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+{
+    /// 上传自定义波形并改用寄存器波形，见 [`lut`](crate::lut)
+    ///
+    /// 复位或 `wake_up` 后需要重新上传。上传后 [`RefreshMode::FastMono`] 使用该波形，
+    /// 见 [`RefreshControl`] 的实现
+    pub async fn set_lut(&mut self, spi: &mut SPI, lut: &Lut<'_>) -> Result<(), SPI::Error> {
+        self.wait_until_idle().await?;
+        for &(register, data) in lut {
+            self.interface.cmd_with_data(spi, register, data).await?;
+        }
+        self.lut_loaded = true;
+        self.set_lut_source(spi, LutSource::Register).await
+    }
+
+    /// 选择使用 OTP 波形或已上传的寄存器波形
+    pub async fn set_lut_source(
+        &mut self,
+        spi: &mut SPI,
+        source: LutSource,
+    ) -> Result<(), SPI::Error> {
+        let reg = match source {
+            LutSource::Otp => 0,
+            LutSource::Register => PANEL_SETTING_REG,
+        };
+        self.lut_source = source;
+        self.interface
+            .cmd_with_data(spi, Command::PanelSetting, &[PANEL_SETTING | reg])
+            .await
+    }
+
+    /// 按刷新方式切换波形来源：快速刷新使用寄存器波形，完整刷新使用 OTP 波形
+    async fn apply_refresh_mode(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        let source = match self.refresh_mode {
+            RefreshMode::FastMono if self.lut_loaded => LutSource::Register,
+            _ => LutSource::Otp,
+        };
+        if source != self.lut_source {
+            self.wait_until_idle().await?;
+            self.set_lut_source(spi, source).await?;
+        }
+        Ok(())
+    }
+
+    impl_power_methods!();
+
+    impl_energy_methods!();
+
+    impl_lvd_methods!();
+
+    impl_frame_methods!(BinaryColor, Command::DataStartTransmission2);
+
+    /// 用指定颜色填充整帧 SRAM，不触发刷新，`BinaryColor::On` 为黑色
+    ///
+    /// 纯色画面用于清屏和消除残影，填充前切换回 OTP 波形，随后的刷新为完整刷新。
+    /// 需要填充图案时使用 [`update_frame_pattern`](Self::update_frame_pattern)
+    pub async fn clear_frame_with(
+        &mut self,
+        spi: &mut SPI,
+        color: BinaryColor,
+    ) -> Result<(), SPI::Error> {
+        if self.lut_source != LutSource::Otp {
+            self.wait_until_idle().await?;
+            self.set_lut_source(spi, LutSource::Otp).await?;
+        }
+        let byte = match color {
+            BinaryColor::On => 0x00,
+            BinaryColor::Off => 0xFF,
+        };
+        self.update_frame_pattern(spi, &[byte]).await
+    }
+
+    impl_color_methods!(
+        BinaryColor,
+        BinaryColor::Off,
+        [BinaryColor::On, BinaryColor::Off]
+    );
+}
+
+impl<SPI, BUSY, DC, RST, DELAY> InternalWiAdditions<SPI, BUSY, DC, RST, DELAY>
+    for Epd7in5<SPI, BUSY, DC, RST, DELAY>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin + Wait,
+    DC: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+{
+    async fn init(&mut self, spi: &mut SPI, delay: &mut DELAY) -> Result<(), SPI::Error> {
+        self.interface.reset(delay, 20_000, 2_000).await;
+
+        self.interface
+            .cmd_with_data(spi, Command::PowerSetting, &[0x07, 0x07, 0x3F, 0x3F])
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::BoosterSoftStart, &[0x17, 0x17, 0x28, 0x17])
+            .await?;
+        self.power_on(spi).await?;
+
+        self.lut_loaded = false;
+        self.set_lut_source(spi, LutSource::Otp).await?;
+        self.interface
+            .cmd_with_data(
+                spi,
+                Command::TconResolution,
+                &[
+                    (WIDTH >> 8) as u8,
+                    WIDTH as u8,
+                    (HEIGHT >> 8) as u8,
+                    HEIGHT as u8,
+                ],
+            )
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::DualSpi, &[0x00])
+            .await?;
+        // DDX=01：新数据 1 为白色，与缓冲区格式一致
+        self.interface
+            .cmd_with_data(spi, Command::VcomAndDataIntervalSetting, &[0x11, 0x07])
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::TconSetting, &[0x22])
+            .await?;
+        self.set_lvd_threshold(spi, self.lvd_threshold()).await?;
+
+        Ok(())
+    }
+}
+
+impl<SPI, BUSY, DC, RST, DELAY> WaveshareDisplay<SPI, BUSY, DC, RST, DELAY>
+    for Epd7in5<SPI, BUSY, DC, RST, DELAY>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin + Wait,
+    DC: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+{
+    type DisplayColor = BinaryColor;
+
+    async fn new(
+        _spi: &mut SPI,
+        busy: BUSY,
+        dc: DC,
+        rst: RST,
+        _delay: &mut DELAY,
+    ) -> Result<Self, SPI::Error> {
+        let interface = DisplayInterface::new(busy, dc, rst);
+
+        let epd = Epd7in5 {
+            interface,
+            lut_loaded: false,
+            lut_source: LutSource::Otp,
+            refresh_mode: RefreshMode::Full,
+            _color: PhantomData,
+            #[cfg(feature = "simulator")]
+            simulator_window: None,
+            #[cfg(feature = "simulator")]
+            simulator_display: SimulatorDisplay::with_default_color(
+                Size::new(WIDTH, HEIGHT),
+                BinaryColor::Off,
+            ),
+        };
+
+        Ok(epd)
+    }
+
+    async fn wake_up(&mut self, spi: &mut SPI, delay: &mut DELAY) -> Result<(), SPI::Error> {
+        self.init(spi, delay).await
+    }
+
+    async fn sleep(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        self.interface.deep_sleep(spi, IS_BUSY_LOW).await
+    }
+
+    #[cfg(feature = "simulator")]
+    async fn update_frame(&mut self, _spi: &mut SPI, buffer: &[u8]) -> Result<(), SPI::Error> {
+        self.draw_simulator(0, buffer.iter().copied());
+        Ok(())
+    }
+
+    #[cfg(not(feature = "simulator"))]
+    async fn update_frame(&mut self, spi: &mut SPI, buffer: &[u8]) -> Result<(), SPI::Error> {
+        self.wait_until_idle().await?;
+        self.interface
+            .cmd_with_data(spi, Command::DataStartTransmission2, &buffer[..FRAME_BYTES])
+            .await?;
+        Ok(())
+    }
+
+    #[cfg(feature = "simulator")]
+    async fn display_frame(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        self.start_refresh(spi).await
+    }
+
+    #[cfg(feature = "simulator")]
+    async fn start_refresh(&mut self, _spi: &mut SPI) -> Result<(), SPI::Error> {
+        if self.simulator_window.is_none() {
+            self.simulator_window = Some(core::cell::RefCell::new(Window::new(
+                &std::format!("EPD Simulator {}x{}", WIDTH, HEIGHT),
+                &OutputSettingsBuilder::new().scale(1).build(),
+            )));
+        }
+        if let Some(window) = &self.simulator_window {
+            window.borrow_mut().update(&self.simulator_display);
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "simulator"))]
+    async fn display_frame(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        self.start_refresh(spi).await?;
+        self.interface.finish_refresh(spi, IS_BUSY_LOW).await
+    }
+
+    #[cfg(not(feature = "simulator"))]
+    async fn start_refresh(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        self.interface.start_refresh(spi, IS_BUSY_LOW, &[]).await
+    }
+
+    async fn update_and_display_frame(
+        &mut self,
+        spi: &mut SPI,
+        buffer: &[u8],
+    ) -> Result<(), SPI::Error> {
+        self.update_frame(spi, buffer).await?;
+        self.display_frame(spi).await?;
+        Ok(())
+    }
+
+    async fn clear_frame(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        self.update_frame_pattern(spi, &[BinaryColor::DEFAULT_COLOR_BYTE])
+            .await
+    }
+
+    fn width(&self) -> u32 {
+        WIDTH
+    }
+
+    fn height(&self) -> u32 {
+        HEIGHT
+    }
+
+    async fn wait_until_idle(&mut self) -> Result<(), SPI::Error> {
+        self.interface.wait_until_idle(IS_BUSY_LOW).await;
+        Ok(())
+    }
+
+    fn is_busy(&mut self) -> bool {
+        self.interface.is_busy(IS_BUSY_LOW)
+    }
+}
+
+impl<SPI, BUSY, DC, RST, DELAY> RefreshControl<SPI> for Epd7in5<SPI, BUSY, DC, RST, DELAY>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin + Wait,
+    DC: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+{
+    /// 通过 [`set_lut`](Epd7in5::set_lut) 上传波形后支持快速刷新：`FastMono` 使用寄存器波形，
+    /// `Full` 使用 OTP 波形；未上传时始终为完整刷新
+    fn set_refresh_mode(&mut self, mode: RefreshMode) -> RefreshMode {
+        self.refresh_mode = if self.lut_loaded {
+            mode
+        } else {
+            RefreshMode::Full
+        };
+        self.refresh_mode
+    }
+
+    async fn display_buffer(&mut self, spi: &mut SPI, buffer: &[u8]) -> Result<(), SPI::Error> {
+        self.apply_refresh_mode(spi).await?;
+        self.update_and_display_frame(spi, buffer).await
+    }
+
+    async fn display_solid(&mut self, spi: &mut SPI, black: bool) -> Result<(), SPI::Error> {
+        let byte = if black {
+            0x00
+        } else {
+            BinaryColor::DEFAULT_COLOR_BYTE
+        };
+        self.apply_refresh_mode(spi).await?;
+        self.update_frame_pattern(spi, &[byte]).await?;
+        self.display_frame(spi).await
+    }
+}
@@ -0,0 +1,655 @@
+//! GDEW075Z08 黑白红电子墨水屏驱动（UC8179 控制器）
+//!
+//! 面板使用两个 1 bpp 平面：黑白平面通过 `DataStartTransmission1`（0x10）传输，
+//! 红色平面通过 `DataStartTransmission2`（0x13）传输
+
+use core::marker::PhantomData;
+
+use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::{digital::Wait, spi::SpiDevice};
+
+use crate::color::{ColorType, TriColor};
+use crate::interface::DisplayInterface;
+use crate::lut::{Lut, LutSource};
+use crate::refresh::{RefreshControl, RefreshMode};
+use crate::traits::{InternalWiAdditions, WaveshareDisplay};
+
+#[cfg(feature = "graphics")]
+use crate::buffer_len;
+use crate::traits;
+
+#[cfg(feature = "simulator")]
+use embedded_graphics_core::prelude::*;
+
+#[cfg(feature = "simulator")]
+extern crate std;
+
+#[cfg(feature = "simulator")]
+use embedded_graphics_simulator::{OutputSettingsBuilder, SimulatorDisplay, Window};
+
+/// 7.5 英寸三色 EPD 完整缓冲区（黑白平面与红色平面）
+#[cfg(feature = "graphics")]
+pub type Display7in5 = crate::graphics::Display<
+    WIDTH,
+    HEIGHT,
+    { buffer_len(WIDTH as usize, HEIGHT as usize * 2) },
+    TriColor,
+>;
+
+/// 面板编号，用于校验 `Display::to_bytes` 导出的缓冲区文件
+pub const PANEL_ID: u16 = 0x075C;
+
+/// 显示宽度
+pub const WIDTH: u32 = 800;
+/// 显示高度
+pub const HEIGHT: u32 = 480;
+
+/// 每行像素数据的字节数
+const ROW_BYTES: usize = WIDTH as usize / 8;
+
+/// 整帧数据的字节数
+const FRAME_BYTES: usize = ROW_BYTES * HEIGHT as usize;
+
+const IS_BUSY_LOW: bool = true;
+
+/// 面板设置：KWR 模式，不含 REG 位
+const PANEL_SETTING: u8 = 0x0F;
+
+/// 面板设置中选择寄存器波形的 REG 位
+const PANEL_SETTING_REG: u8 = 0x20;
+
+/// EPD 命令
+#[derive(Copy, Clone)]
+#[allow(unused)]
+pub(crate) enum Command {
+    /// 面板设置
+    PanelSetting = 0x00,
+    /// 电源设置
+    PowerSetting = 0x01,
+    /// 关闭电源
+    PowerOff = 0x02,
+    /// 开启电源
+    PowerOn = 0x04,
+    /// 启动数据传输
+    BoosterSoftStart = 0x06,
+    /// 深度睡眠
+    DeepSleep = 0x07,
+    /// 开始传输数据（旧数据）
+    DataStartTransmission1 = 0x10,
+    /// 停止数据传输
+    DataStop = 0x11,
+    /// 显示刷新
+    DisplayRefresh = 0x12,
+    /// 开始传输数据（新数据）
+    DataStartTransmission2 = 0x13,
+    /// 双 SPI 模式
+    DualSpi = 0x15,
+    /// PLL 控制
+    PllControl = 0x30,
+    /// 温度传感器
+    TemperatureSensor = 0x40,
+    /// 温度校准
+    TemperatureCalibration = 0x41,
+    /// 温度传感器写入
+    TemperatureSensorWrite = 0x42,
+    /// 温度传感器读取
+    TemperatureSensorRead = 0x43,
+    /// 神秘命令 1
+    MisteryCommand1 = 0x4D,
+    /// VCOM 和数据间隔设置
+    VcomAndDataIntervalSetting = 0x50,
+    /// 低功耗检测
+    LowPowerDetection = 0x51,
+    /// TCON 设置
+    TconSetting = 0x60,
+    /// TCON 分辨率
+    TconResolution = 0x61,
+    /// SPI Flash 控制
+    SpiFlashControl = 0x65,
+    /// 版本
+    Revision = 0x70,
+    /// 自动测量 VCOM
+    AutoMeasurementVcom = 0x80,
+    /// 读取 VCOM 值
+    ReadVcomValue = 0x81,
+    /// VCOM DC 设置
+    VcmDcSetting = 0x82,
+    /// 部分窗口
+    PartialWindow = 0x83,
+    /// 编程模式
+    ProgramMode = 0x90,
+    /// 激活编程
+    ActiveProgram = 0x91,
+    /// 读取 MTP 数据
+    ReadMTPData = 0x92,
+    /// MTP 编程配置
+    MtpProgramConfig = 0xA2,
+    /// 级联设置
+    CascadeSetting = 0xE0,
+    /// 省电设置
+    PowerSavingSetting = 0xE3,
+    /// LVD 电压选择
+    LvdVoltageSelect = 0xE4,
+    /// 神秘命令 2
+    MisteryCommand2 = 0xE9,
+}
+
+impl traits::Command for Command {
+    fn address(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Epd7in5 (gdew075z08) 三色驱动
+pub struct Epd7in5<SPI, BUSY, DC, RST, DELAY> {
+    interface: DisplayInterface<SPI, BUSY, DC, RST, DELAY>,
+    _color: PhantomData<TriColor>,
+    #[cfg(feature = "simulator")]
+    simulator_window: Option<core::cell::RefCell<Window>>,
+    #[cfg(feature = "simulator")]
+    simulator_display: SimulatorDisplay<TriColor>,
+}
+
+impl<SPI, BUSY, DC, RST, DELAY> Epd7in5<SPI, BUSY, DC, RST, DELAY>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin + Wait,
+    DC: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+{
+    /// 上传自定义波形并改用寄存器波形，见 [`lut`](crate::lut)
+    ///
+    /// 本驱动不附带波形表，`lut` 需由调用者提供经过验证的三色波形；
+    /// 复位或 `wake_up` 后需要重新上传
+    pub async fn set_lut(&mut self, spi: &mut SPI, lut: &Lut<'_>) -> Result<(), SPI::Error> {
+        self.wait_until_idle().await?;
+        for &(register, data) in lut {
+            self.interface.cmd_with_data(spi, register, data).await?;
+        }
+        self.set_lut_source(spi, LutSource::Register).await
+    }
+
+    /// 选择使用 OTP 波形或已上传的寄存器波形
+    pub async fn set_lut_source(
+        &mut self,
+        spi: &mut SPI,
+        source: LutSource,
+    ) -> Result<(), SPI::Error> {
+        let reg = match source {
+            LutSource::Otp => 0,
+            LutSource::Register => PANEL_SETTING_REG,
+        };
+        self.interface
+            .cmd_with_data(spi, Command::PanelSetting, &[PANEL_SETTING | reg])
+            .await
+    }
+
+    impl_power_methods!();
+
+    impl_energy_methods!();
+
+    impl_lvd_methods!();
+
+    /// 传输黑白平面，不触发刷新
+    #[cfg(not(feature = "simulator"))]
+    pub async fn update_achromatic_frame(
+        &mut self,
+        spi: &mut SPI,
+        black: &[u8],
+    ) -> Result<(), SPI::Error> {
+        self.wait_until_idle().await?;
+        self.interface
+            .cmd_with_data(spi, Command::DataStartTransmission1, &black[..FRAME_BYTES])
+            .await
+    }
+
+    /// 传输黑白平面，不触发刷新
+    #[cfg(feature = "simulator")]
+    pub async fn update_achromatic_frame(
+        &mut self,
+        _spi: &mut SPI,
+        black: &[u8],
+    ) -> Result<(), SPI::Error> {
+        self.draw_simulator(0, black[..FRAME_BYTES].iter().copied(), achromatic_color);
+        Ok(())
+    }
+
+    /// 传输红色平面，不触发刷新
+    #[cfg(not(feature = "simulator"))]
+    pub async fn update_chromatic_frame(
+        &mut self,
+        spi: &mut SPI,
+        chromatic: &[u8],
+    ) -> Result<(), SPI::Error> {
+        self.wait_until_idle().await?;
+        self.interface
+            .cmd_with_data(
+                spi,
+                Command::DataStartTransmission2,
+                &chromatic[..FRAME_BYTES],
+            )
+            .await
+    }
+
+    /// 传输红色平面，不触发刷新
+    #[cfg(feature = "simulator")]
+    pub async fn update_chromatic_frame(
+        &mut self,
+        _spi: &mut SPI,
+        chromatic: &[u8],
+    ) -> Result<(), SPI::Error> {
+        self.draw_simulator(0, chromatic[..FRAME_BYTES].iter().copied(), chromatic_color);
+        Ok(())
+    }
+
+    /// 依次传输黑白平面和红色平面，不触发刷新
+    pub async fn update_color_frame(
+        &mut self,
+        spi: &mut SPI,
+        black: &[u8],
+        chromatic: &[u8],
+    ) -> Result<(), SPI::Error> {
+        self.update_achromatic_frame(spi, black).await?;
+        self.update_chromatic_frame(spi, chromatic).await
+    }
+
+    /// 用循环重复的字节图案分别填充黑白平面和红色平面，不触发刷新
+    ///
+    /// 图案按平面的打包格式解释，长度为行字节数（`WIDTH / 8`）整数倍的图案得到横条纹
+    #[cfg(not(feature = "simulator"))]
+    pub async fn update_frame_pattern(
+        &mut self,
+        spi: &mut SPI,
+        black: &[u8],
+        chromatic: &[u8],
+    ) -> Result<(), SPI::Error> {
+        self.wait_until_idle().await?;
+        self.interface
+            .cmd(spi, Command::DataStartTransmission1)
+            .await?;
+        self.interface.data_pattern(spi, black, FRAME_BYTES).await?;
+        self.interface
+            .cmd(spi, Command::DataStartTransmission2)
+            .await?;
+        self.interface
+            .data_pattern(spi, chromatic, FRAME_BYTES)
+            .await
+    }
+
+    /// 用循环重复的字节图案分别填充黑白平面和红色平面，不触发刷新
+    #[cfg(feature = "simulator")]
+    pub async fn update_frame_pattern(
+        &mut self,
+        _spi: &mut SPI,
+        black: &[u8],
+        chromatic: &[u8],
+    ) -> Result<(), SPI::Error> {
+        let black = black.iter().copied().cycle().take(FRAME_BYTES);
+        self.draw_simulator(0, black, achromatic_color);
+        let chromatic = chromatic.iter().copied().cycle().take(FRAME_BYTES);
+        self.draw_simulator(0, chromatic, chromatic_color);
+        Ok(())
+    }
+
+    /// 用指定颜色填充两个平面，不触发刷新
+    ///
+    /// 需要填充图案时使用 [`update_frame_pattern`](Self::update_frame_pattern)
+    pub async fn clear_frame_with(
+        &mut self,
+        spi: &mut SPI,
+        color: TriColor,
+    ) -> Result<(), SPI::Error> {
+        let (black, chromatic) = match color {
+            TriColor::Black => (0x00, 0x00),
+            TriColor::White => (0xFF, 0x00),
+            TriColor::Chromatic => (0xFF, 0xFF),
+        };
+        self.update_frame_pattern(spi, &[black], &[chromatic]).await
+    }
+
+    impl_color_methods!(
+        TriColor,
+        TriColor::White,
+        [TriColor::Black, TriColor::White, TriColor::Chromatic]
+    );
+
+    /// 逐行生成并传输黑白平面，无需完整的帧缓冲区
+    ///
+    /// `fill_row(y, row)` 依次以行号 `0..HEIGHT` 调用，`row` 调用前已填充为白色
+    pub async fn update_achromatic_frame_from_rows<F>(
+        &mut self,
+        spi: &mut SPI,
+        fill_row: F,
+    ) -> Result<(), SPI::Error>
+    where
+        F: FnMut(u32, &mut [u8]),
+    {
+        self.update_plane_from_rows(spi, Command::DataStartTransmission1, fill_row)
+            .await
+    }
+
+    /// 逐行生成并传输红色平面，无需完整的帧缓冲区
+    ///
+    /// `fill_row(y, row)` 依次以行号 `0..HEIGHT` 调用，`row` 调用前已填充为无红色
+    pub async fn update_chromatic_frame_from_rows<F>(
+        &mut self,
+        spi: &mut SPI,
+        fill_row: F,
+    ) -> Result<(), SPI::Error>
+    where
+        F: FnMut(u32, &mut [u8]),
+    {
+        self.update_plane_from_rows(spi, Command::DataStartTransmission2, fill_row)
+            .await
+    }
+
+    /// 从字节迭代器流式传输整帧数据
+    ///
+    /// 字节依次为黑白平面和红色平面，与 [`Display7in5`] 的缓冲区相同；
+    /// 不足两个平面时剩余部分为白色，多余的字节被忽略
+    pub async fn update_frame_from_iter<I>(
+        &mut self,
+        spi: &mut SPI,
+        bytes: I,
+    ) -> Result<(), SPI::Error>
+    where
+        I: IntoIterator<Item = u8>,
+    {
+        let mut bytes = bytes.into_iter();
+        let mut fill = |_, row: &mut [u8]| {
+            for (byte, value) in row.iter_mut().zip(&mut bytes) {
+                *byte = value;
+            }
+        };
+        self.update_achromatic_frame_from_rows(spi, &mut fill)
+            .await?;
+        self.update_chromatic_frame_from_rows(spi, &mut fill).await
+    }
+
+    /// 以 `command` 开始传输一个平面，逐行调用 `fill_row` 生成数据
+    #[cfg(not(feature = "simulator"))]
+    async fn update_plane_from_rows<F>(
+        &mut self,
+        spi: &mut SPI,
+        command: Command,
+        mut fill_row: F,
+    ) -> Result<(), SPI::Error>
+    where
+        F: FnMut(u32, &mut [u8]),
+    {
+        self.wait_until_idle().await?;
+        self.interface.cmd(spi, command).await?;
+        let mut row = [0u8; ROW_BYTES];
+        for y in 0..HEIGHT {
+            row.fill(plane_default_byte(command));
+            fill_row(y, &mut row);
+            self.interface.data(spi, &row).await?;
+        }
+        Ok(())
+    }
+
+    /// 以 `command` 开始传输一个平面，逐行调用 `fill_row` 生成数据
+    #[cfg(feature = "simulator")]
+    async fn update_plane_from_rows<F>(
+        &mut self,
+        _spi: &mut SPI,
+        command: Command,
+        mut fill_row: F,
+    ) -> Result<(), SPI::Error>
+    where
+        F: FnMut(u32, &mut [u8]),
+    {
+        let color = match command {
+            Command::DataStartTransmission2 => chromatic_color,
+            _ => achromatic_color,
+        };
+        let mut row = [0u8; ROW_BYTES];
+        for y in 0..HEIGHT {
+            row.fill(plane_default_byte(command));
+            fill_row(y, &mut row);
+            self.draw_simulator(y, row.iter().copied(), color);
+        }
+        Ok(())
+    }
+
+    /// 将从第 `first_row` 行开始的平面数据绘制到模拟器，`color(bit)` 返回 `None` 的像素保持不变
+    #[cfg(feature = "simulator")]
+    fn draw_simulator(
+        &mut self,
+        first_row: u32,
+        bytes: impl Iterator<Item = u8>,
+        color: fn(bool) -> Option<TriColor>,
+    ) {
+        let color_iter = bytes.flat_map(|byte| (0..8).map(move |shift| byte >> shift & 0x01 != 0));
+
+        let pixels = color_iter.enumerate().filter_map(|(i, bit)| {
+            let x = (i % WIDTH as usize) as i32;
+            let y = (first_row as usize + i / WIDTH as usize) as i32;
+            Some(Pixel(Point::new(x, y), color(bit)?))
+        });
+
+        self.simulator_display
+            .draw_iter(pixels)
+            .expect("Failed to draw frame to EPD simulator");
+    }
+}
+
+/// 平面在未写入像素时的填充字节
+fn plane_default_byte(command: Command) -> u8 {
+    match command {
+        Command::DataStartTransmission2 => TriColor::DEFAULT_CHROMATIC_BYTE,
+        _ => TriColor::DEFAULT_COLOR_BYTE,
+    }
+}
+
+/// 黑白平面中一位对应的模拟器颜色
+#[cfg(feature = "simulator")]
+fn achromatic_color(bit: bool) -> Option<TriColor> {
+    Some(if bit {
+        TriColor::White
+    } else {
+        TriColor::Black
+    })
+}
+
+/// 红色平面中一位对应的模拟器颜色，未置位的像素保持黑白平面的颜色
+#[cfg(feature = "simulator")]
+fn chromatic_color(bit: bool) -> Option<TriColor> {
+    bit.then_some(TriColor::Chromatic)
+}
+
+impl<SPI, BUSY, DC, RST, DELAY> InternalWiAdditions<SPI, BUSY, DC, RST, DELAY>
+    for Epd7in5<SPI, BUSY, DC, RST, DELAY>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin + Wait,
+    DC: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+{
+    async fn init(&mut self, spi: &mut SPI, delay: &mut DELAY) -> Result<(), SPI::Error> {
+        self.interface.reset(delay, 20_000, 2_000).await;
+
+        self.interface
+            .cmd_with_data(spi, Command::PowerSetting, &[0x07, 0x07, 0x3F, 0x3F])
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::BoosterSoftStart, &[0x17, 0x17, 0x28, 0x17])
+            .await?;
+        self.power_on(spi).await?;
+
+        self.set_lut_source(spi, LutSource::Otp).await?;
+        self.interface
+            .cmd_with_data(
+                spi,
+                Command::TconResolution,
+                &[
+                    (WIDTH >> 8) as u8,
+                    WIDTH as u8,
+                    (HEIGHT >> 8) as u8,
+                    HEIGHT as u8,
+                ],
+            )
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::DualSpi, &[0x00])
+            .await?;
+        // DDX=01：黑白平面 1 为白色，红色平面 1 为红色
+        self.interface
+            .cmd_with_data(spi, Command::VcomAndDataIntervalSetting, &[0x11, 0x07])
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::TconSetting, &[0x22])
+            .await?;
+        self.set_lvd_threshold(spi, self.lvd_threshold()).await?;
+
+        Ok(())
+    }
+}
+
+impl<SPI, BUSY, DC, RST, DELAY> WaveshareDisplay<SPI, BUSY, DC, RST, DELAY>
+    for Epd7in5<SPI, BUSY, DC, RST, DELAY>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin + Wait,
+    DC: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+{
+    type DisplayColor = TriColor;
+
+    async fn new(
+        _spi: &mut SPI,
+        busy: BUSY,
+        dc: DC,
+        rst: RST,
+        _delay: &mut DELAY,
+    ) -> Result<Self, SPI::Error> {
+        let interface = DisplayInterface::new(busy, dc, rst);
+
+        let epd = Epd7in5 {
+            interface,
+            _color: PhantomData,
+            #[cfg(feature = "simulator")]
+            simulator_window: None,
+            #[cfg(feature = "simulator")]
+            simulator_display: SimulatorDisplay::with_default_color(
+                Size::new(WIDTH, HEIGHT),
+                TriColor::White,
+            ),
+        };
+
+        Ok(epd)
+    }
+
+    async fn wake_up(&mut self, spi: &mut SPI, delay: &mut DELAY) -> Result<(), SPI::Error> {
+        self.init(spi, delay).await
+    }
+
+    async fn sleep(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        self.interface.deep_sleep(spi, IS_BUSY_LOW).await
+    }
+
+    /// `buffer` 依次为黑白平面和红色平面，与 [`Display7in5`] 的缓冲区相同
+    async fn update_frame(&mut self, spi: &mut SPI, buffer: &[u8]) -> Result<(), SPI::Error> {
+        let (black, chromatic) = buffer.split_at(FRAME_BYTES);
+        self.update_color_frame(spi, black, chromatic).await
+    }
+
+    #[cfg(feature = "simulator")]
+    async fn display_frame(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        self.start_refresh(spi).await
+    }
+
+    #[cfg(feature = "simulator")]
+    async fn start_refresh(&mut self, _spi: &mut SPI) -> Result<(), SPI::Error> {
+        if self.simulator_window.is_none() {
+            self.simulator_window = Some(core::cell::RefCell::new(Window::new(
+                &std::format!("EPD Simulator {}x{}", WIDTH, HEIGHT),
+                &OutputSettingsBuilder::new().scale(1).build(),
+            )));
+        }
+        if let Some(window) = &self.simulator_window {
+            window.borrow_mut().update(&self.simulator_display);
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "simulator"))]
+    async fn display_frame(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        self.start_refresh(spi).await?;
+        self.interface.finish_refresh(spi, IS_BUSY_LOW).await
+    }
+
+    #[cfg(not(feature = "simulator"))]
+    async fn start_refresh(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        self.interface.start_refresh(spi, IS_BUSY_LOW, &[]).await
+    }
+
+    async fn update_and_display_frame(
+        &mut self,
+        spi: &mut SPI,
+        buffer: &[u8],
+    ) -> Result<(), SPI::Error> {
+        self.update_frame(spi, buffer).await?;
+        self.display_frame(spi).await?;
+        Ok(())
+    }
+
+    async fn clear_frame(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        self.update_frame_pattern(
+            spi,
+            &[TriColor::DEFAULT_COLOR_BYTE],
+            &[TriColor::DEFAULT_CHROMATIC_BYTE],
+        )
+        .await
+    }
+
+    fn width(&self) -> u32 {
+        WIDTH
+    }
+
+    fn height(&self) -> u32 {
+        HEIGHT
+    }
+
+    async fn wait_until_idle(&mut self) -> Result<(), SPI::Error> {
+        self.interface.wait_until_idle(IS_BUSY_LOW).await;
+        Ok(())
+    }
+
+    fn is_busy(&mut self) -> bool {
+        self.interface.is_busy(IS_BUSY_LOW)
+    }
+}
+
+impl<SPI, BUSY, DC, RST, DELAY> RefreshControl<SPI> for Epd7in5<SPI, BUSY, DC, RST, DELAY>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin + Wait,
+    DC: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+{
+    /// 仅支持完整刷新
+    fn set_refresh_mode(&mut self, _mode: RefreshMode) -> RefreshMode {
+        RefreshMode::Full
+    }
+
+    async fn display_buffer(&mut self, spi: &mut SPI, buffer: &[u8]) -> Result<(), SPI::Error> {
+        self.update_and_display_frame(spi, buffer).await
+    }
+
+    async fn display_solid(&mut self, spi: &mut SPI, black: bool) -> Result<(), SPI::Error> {
+        let byte = if black {
+            0x00
+        } else {
+            TriColor::DEFAULT_COLOR_BYTE
+        };
+        self.update_frame_pattern(spi, &[byte], &[TriColor::DEFAULT_CHROMATIC_BYTE])
+            .await?;
+        self.display_frame(spi).await
+    }
+}
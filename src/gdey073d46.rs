@@ -0,0 +1,408 @@
+//! GDEY073D46 七色 ACeP 电子墨水屏驱动
+//!
+//! 控制器没有公开的低电压检测命令，本驱动不提供 LVD 相关方法，见 [`lvd`](crate::lvd)
+
+use core::marker::PhantomData;
+
+use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::{digital::Wait, spi::SpiDevice};
+
+use crate::color::{ColorType, SevenColor};
+use crate::interface::DisplayInterface;
+use crate::refresh::{RefreshControl, RefreshMode};
+use crate::traits::{InternalWiAdditions, WaveshareDisplay};
+
+#[cfg(feature = "graphics")]
+use crate::buffer_len;
+use crate::traits;
+
+#[cfg(feature = "simulator")]
+use embedded_graphics_core::prelude::*;
+
+#[cfg(feature = "simulator")]
+extern crate std;
+
+#[cfg(feature = "simulator")]
+use embedded_graphics_simulator::{OutputSettingsBuilder, SimulatorDisplay, Window};
+
+/// 7.3 英寸七色 EPD 完整缓冲区
+#[cfg(feature = "graphics")]
+pub type Display7in3f = crate::graphics::Display<
+    WIDTH,
+    HEIGHT,
+    { buffer_len(WIDTH as usize, HEIGHT as usize * 4) },
+    SevenColor,
+>;
+
+/// 面板编号，用于校验 `Display::to_bytes` 导出的缓冲区文件
+pub const PANEL_ID: u16 = 0x073F;
+
+/// 显示宽度
+pub const WIDTH: u32 = 800;
+/// 显示高度
+pub const HEIGHT: u32 = 480;
+
+/// 每行像素数据的字节数
+const ROW_BYTES: usize = WIDTH as usize / 2;
+
+/// 整帧数据的字节数
+const FRAME_BYTES: usize = ROW_BYTES * HEIGHT as usize;
+
+const IS_BUSY_LOW: bool = true;
+
+/// EPD 命令
+#[derive(Copy, Clone)]
+#[allow(unused)]
+pub(crate) enum Command {
+    /// 面板设置
+    PanelSetting = 0x00,
+    /// 电源设置
+    PowerSetting = 0x01,
+    /// 关闭电源
+    PowerOff = 0x02,
+    /// 关闭电源时序设置
+    PowerOffSequenceSetting = 0x03,
+    /// 开启电源
+    PowerOn = 0x04,
+    /// 升压软启动 1
+    BoosterSoftStart1 = 0x05,
+    /// 升压软启动 2
+    BoosterSoftStart2 = 0x06,
+    /// 深度睡眠
+    DeepSleep = 0x07,
+    /// 升压软启动 3
+    BoosterSoftStart3 = 0x08,
+    /// 开始传输数据
+    DataStartTransmission = 0x10,
+    /// 显示刷新
+    DisplayRefresh = 0x12,
+    /// 内部电源控制
+    InternalPowerControl = 0x13,
+    /// PLL 控制
+    PllControl = 0x30,
+    /// 温度传感器选择
+    TemperatureSensorSelect = 0x41,
+    /// VCOM 和数据间隔设置
+    VcomAndDataIntervalSetting = 0x50,
+    /// TCON 设置
+    TconSetting = 0x60,
+    /// TCON 分辨率
+    TconResolution = 0x61,
+    /// VCOM DC 设置
+    VcmDcSetting = 0x82,
+    /// VCOM DC 时序设置
+    VcmDcTimingSetting = 0x84,
+    /// 自动增益设置
+    AutoGateSetting = 0x86,
+    /// 命令解锁
+    Unlock = 0xAA,
+    /// 级联设置
+    CascadeSetting = 0xE0,
+    /// 省电设置
+    PowerSavingSetting = 0xE3,
+    /// 强制温度设置
+    ForceTemperature = 0xE6,
+}
+
+impl traits::Command for Command {
+    fn address(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Epd7in3f (gdey073d46) 七色驱动
+pub struct Epd7in3f<SPI, BUSY, DC, RST, DELAY> {
+    interface: DisplayInterface<SPI, BUSY, DC, RST, DELAY>,
+    _color: PhantomData<SevenColor>,
+    #[cfg(feature = "simulator")]
+    simulator_window: Option<core::cell::RefCell<Window>>,
+    #[cfg(feature = "simulator")]
+    simulator_display: SimulatorDisplay<SevenColor>,
+}
+
+impl<SPI, BUSY, DC, RST, DELAY> Epd7in3f<SPI, BUSY, DC, RST, DELAY>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin + Wait,
+    DC: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+{
+    impl_power_methods!();
+
+    impl_energy_methods!();
+
+    impl_frame_methods!(SevenColor, Command::DataStartTransmission);
+
+    /// 用指定颜色填充整帧 SRAM，不触发刷新
+    ///
+    /// 需要填充图案时使用 [`update_frame_pattern`](Self::update_frame_pattern)
+    pub async fn clear_frame_with(
+        &mut self,
+        spi: &mut SPI,
+        color: SevenColor,
+    ) -> Result<(), SPI::Error> {
+        self.update_frame_pattern(spi, &[color.bits() * 0x11]).await
+    }
+
+    impl_color_methods!(SevenColor, SevenColor::White, SevenColor::ALL);
+}
+
+impl<SPI, BUSY, DC, RST, DELAY> InternalWiAdditions<SPI, BUSY, DC, RST, DELAY>
+    for Epd7in3f<SPI, BUSY, DC, RST, DELAY>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin + Wait,
+    DC: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+{
+    async fn init(&mut self, spi: &mut SPI, delay: &mut DELAY) -> Result<(), SPI::Error> {
+        self.interface.reset(delay, 20_000, 2_000).await;
+        self.wait_until_idle().await?;
+        delay.delay_ms(30).await;
+
+        self.interface
+            .cmd_with_data(spi, Command::Unlock, &[0x49, 0x55, 0x20, 0x08, 0x09, 0x18])
+            .await?;
+        self.interface
+            .cmd_with_data(
+                spi,
+                Command::PowerSetting,
+                &[0x3F, 0x00, 0x32, 0x2A, 0x0E, 0x2A],
+            )
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::PanelSetting, &[0x5F, 0x69])
+            .await?;
+        self.interface
+            .cmd_with_data(
+                spi,
+                Command::PowerOffSequenceSetting,
+                &[0x00, 0x54, 0x00, 0x44],
+            )
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::BoosterSoftStart1, &[0x40, 0x1F, 0x1F, 0x2C])
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::BoosterSoftStart2, &[0x6F, 0x1F, 0x1F, 0x22])
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::BoosterSoftStart3, &[0x6F, 0x1F, 0x1F, 0x22])
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::InternalPowerControl, &[0x00, 0x04])
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::PllControl, &[0x3C])
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::TemperatureSensorSelect, &[0x00])
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::VcomAndDataIntervalSetting, &[0x3F])
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::TconSetting, &[0x02, 0x00])
+            .await?;
+        self.interface
+            .cmd_with_data(
+                spi,
+                Command::TconResolution,
+                &[
+                    (WIDTH >> 8) as u8,
+                    WIDTH as u8,
+                    (HEIGHT >> 8) as u8,
+                    HEIGHT as u8,
+                ],
+            )
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::VcmDcSetting, &[0x1E])
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::VcmDcTimingSetting, &[0x00])
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::AutoGateSetting, &[0x00])
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::PowerSavingSetting, &[0x2F])
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::CascadeSetting, &[0x00])
+            .await?;
+        self.interface
+            .cmd_with_data(spi, Command::ForceTemperature, &[0x00])
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl<SPI, BUSY, DC, RST, DELAY> WaveshareDisplay<SPI, BUSY, DC, RST, DELAY>
+    for Epd7in3f<SPI, BUSY, DC, RST, DELAY>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin + Wait,
+    DC: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+{
+    type DisplayColor = SevenColor;
+
+    async fn new(
+        _spi: &mut SPI,
+        busy: BUSY,
+        dc: DC,
+        rst: RST,
+        _delay: &mut DELAY,
+    ) -> Result<Self, SPI::Error> {
+        let mut interface = DisplayInterface::new(busy, dc, rst);
+        interface.set_auto_power_off(true);
+
+        let epd = Epd7in3f {
+            interface,
+            _color: PhantomData,
+            #[cfg(feature = "simulator")]
+            simulator_window: None,
+            #[cfg(feature = "simulator")]
+            simulator_display: SimulatorDisplay::with_default_color(
+                Size::new(WIDTH, HEIGHT),
+                SevenColor::White,
+            ),
+        };
+
+        Ok(epd)
+    }
+
+    async fn wake_up(&mut self, spi: &mut SPI, delay: &mut DELAY) -> Result<(), SPI::Error> {
+        self.init(spi, delay).await
+    }
+
+    async fn sleep(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        self.interface.deep_sleep(spi, IS_BUSY_LOW).await
+    }
+
+    #[cfg(feature = "simulator")]
+    async fn update_frame(&mut self, _spi: &mut SPI, buffer: &[u8]) -> Result<(), SPI::Error> {
+        self.draw_simulator(0, buffer.iter().copied());
+        Ok(())
+    }
+
+    #[cfg(not(feature = "simulator"))]
+    async fn update_frame(&mut self, spi: &mut SPI, buffer: &[u8]) -> Result<(), SPI::Error> {
+        self.wait_until_idle().await?;
+        self.interface
+            .cmd_with_data(
+                spi,
+                Command::DataStartTransmission,
+                &buffer[..(FRAME_BYTES)],
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[cfg(feature = "simulator")]
+    async fn display_frame(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        self.start_refresh(spi).await
+    }
+
+    #[cfg(feature = "simulator")]
+    async fn start_refresh(&mut self, _spi: &mut SPI) -> Result<(), SPI::Error> {
+        if self.simulator_window.is_none() {
+            self.simulator_window = Some(core::cell::RefCell::new(Window::new(
+                &std::format!("EPD Simulator {}x{}", WIDTH, HEIGHT),
+                &OutputSettingsBuilder::new().scale(1).build(),
+            )));
+        }
+        if let Some(window) = &self.simulator_window {
+            window.borrow_mut().update(&self.simulator_display);
+        }
+        Ok(())
+    }
+
+    /// 刷新前上电，默认在刷新完成后断电，避免高压长时间施加在面板上，
+    /// 见 [`set_auto_power_off`](Epd7in3f::set_auto_power_off)
+    #[cfg(not(feature = "simulator"))]
+    async fn display_frame(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        self.start_refresh(spi).await?;
+        self.interface.finish_refresh(spi, IS_BUSY_LOW).await
+    }
+
+    /// 上电后发送刷新命令；与 `display_frame` 不同，刷新完成后面板仍保持上电
+    #[cfg(not(feature = "simulator"))]
+    async fn start_refresh(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        self.interface
+            .start_refresh(spi, IS_BUSY_LOW, &[0x00])
+            .await
+    }
+
+    async fn update_and_display_frame(
+        &mut self,
+        spi: &mut SPI,
+        buffer: &[u8],
+    ) -> Result<(), SPI::Error> {
+        self.update_frame(spi, buffer).await?;
+        self.display_frame(spi).await?;
+        Ok(())
+    }
+
+    async fn clear_frame(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        self.wait_until_idle().await?;
+
+        self.interface
+            .cmd(spi, Command::DataStartTransmission)
+            .await?;
+        self.interface
+            .data_x_times(spi, SevenColor::DEFAULT_COLOR_BYTE, FRAME_BYTES as u32)
+            .await
+    }
+
+    fn width(&self) -> u32 {
+        WIDTH
+    }
+
+    fn height(&self) -> u32 {
+        HEIGHT
+    }
+
+    async fn wait_until_idle(&mut self) -> Result<(), SPI::Error> {
+        self.interface.wait_until_idle(IS_BUSY_LOW).await;
+        Ok(())
+    }
+
+    fn is_busy(&mut self) -> bool {
+        self.interface.is_busy(IS_BUSY_LOW)
+    }
+}
+
+impl<SPI, BUSY, DC, RST, DELAY> RefreshControl<SPI> for Epd7in3f<SPI, BUSY, DC, RST, DELAY>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin + Wait,
+    DC: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+{
+    /// 仅支持完整刷新
+    fn set_refresh_mode(&mut self, _mode: RefreshMode) -> RefreshMode {
+        RefreshMode::Full
+    }
+
+    async fn display_buffer(&mut self, spi: &mut SPI, buffer: &[u8]) -> Result<(), SPI::Error> {
+        self.update_and_display_frame(spi, buffer).await
+    }
+
+    async fn display_solid(&mut self, spi: &mut SPI, black: bool) -> Result<(), SPI::Error> {
+        let byte = if black {
+            0x00
+        } else {
+            SevenColor::DEFAULT_COLOR_BYTE
+        };
+        self.update_frame_pattern(spi, &[byte]).await?;
+        self.display_frame(spi).await
+    }
+}
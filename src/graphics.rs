@@ -1,12 +1,66 @@
 //! EPD 显示图形支持
 
 use crate::color::{ColorType, QuadColor};
+use crate::image::QuadImage;
 use core::marker::PhantomData;
 use embedded_graphics_core::prelude::*;
+use embedded_graphics_core::primitives::Rectangle;
 
 /// 计算每行字节数（考虑填充位）
 const fn line_bytes(width: u32, bits_per_pixel: usize) -> usize {
-    (width as usize * bits_per_pixel + 7) / 8
+    (width as usize * bits_per_pixel).div_ceil(8)
+}
+
+/// 缓冲区文件魔数
+pub const BUFFER_MAGIC: [u8; 4] = *b"EPDB";
+
+/// 缓冲区文件头长度
+///
+/// | 偏移 | 长度 | 内容                          |
+/// |------|------|-------------------------------|
+/// | 0    | 4    | 魔数 `b"EPDB"`                 |
+/// | 4    | 2    | 宽度（u16 小端）               |
+/// | 6    | 2    | 高度（u16 小端）               |
+/// | 8    | 1    | 每像素位数（低 4 位）与平面数减一（高 4 位） |
+/// | 9    | 1    | 位序，见 [`BitOrder`]          |
+/// | 10   | 2    | 面板编号（u16 小端），0 表示不限 |
+/// | 12   | ..   | 缓冲区数据                    |
+pub const BUFFER_HEADER_LEN: usize = 12;
+
+/// 字节内像素的排列顺序
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum BitOrder {
+    /// 最左侧像素位于高位（面板 SRAM 格式）
+    MsbFirst = 0,
+    /// 最左侧像素位于低位（模拟器格式）
+    LsbFirst = 1,
+}
+
+impl BitOrder {
+    /// 当前构建中 `Display` 使用的位序
+    pub const NATIVE: BitOrder = if cfg!(feature = "simulator") {
+        BitOrder::LsbFirst
+    } else {
+        BitOrder::MsbFirst
+    };
+}
+
+/// 载入缓冲区文件时的错误
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BufferError {
+    /// 魔数不匹配或数据短于文件头
+    BadMagic,
+    /// 宽高与显示不符
+    SizeMismatch,
+    /// 每像素位数与颜色类型不符
+    BppMismatch,
+    /// 未知的位序
+    BadBitOrder,
+    /// 面板编号不符
+    PanelMismatch,
+    /// 数据长度与文件头不符
+    LengthMismatch,
 }
 
 /// 用于 embedded graphics 的显示缓冲区
@@ -14,7 +68,11 @@ const fn line_bytes(width: u32, bits_per_pixel: usize) -> usize {
 /// - WIDTH: 显示宽度（像素）
 /// - HEIGHT: 显示高度（像素）
 /// - COLOR: 目标显示使用的颜色类型
-/// - BYTECOUNT: 缓冲区字节数
+/// - BYTECOUNT: 缓冲区字节数，双平面颜色为两个平面之和
+///
+/// 双平面颜色（如 [`TriColor`](crate::color::TriColor)）的前半部分为黑白平面，
+/// 后半部分为彩色平面，分别通过 [`bw_buffer`](Self::bw_buffer) 和
+/// [`chromatic_buffer`](Self::chromatic_buffer) 访问
 pub struct Display<
     const WIDTH: u32,
     const HEIGHT: u32,
@@ -35,8 +93,12 @@ impl<
     /// 初始化显示缓冲区，默认为白色
     #[inline(always)]
     fn default() -> Self {
+        let mut buffer = [COLOR::DEFAULT_COLOR_BYTE; BYTECOUNT];
+        if COLOR::BUFFER_COUNT == 2 {
+            buffer[BYTECOUNT / 2..].fill(COLOR::DEFAULT_CHROMATIC_BYTE);
+        }
         Self {
-            buffer: [QuadColor::default_color_byte(); BYTECOUNT],
+            buffer,
             _color: PhantomData,
         }
     }
@@ -89,10 +151,383 @@ impl<
         &self.buffer
     }
 
+    /// 以其他颜色类型绘制，颜色通过 `Into<COLOR>` 转换
+    pub fn color_converted<C>(&mut self) -> ColorConverted<'_, Self, C>
+    where
+        C: PixelColor + Into<COLOR>,
+    {
+        ColorConverted::new(self)
+    }
+
+    /// 获取黑白平面（单平面颜色即为整个缓冲区）
+    pub fn bw_buffer(&self) -> &[u8] {
+        &self.buffer[..BYTECOUNT / COLOR::BUFFER_COUNT]
+    }
+
+    /// 获取彩色平面，单平面颜色返回空切片
+    pub fn chromatic_buffer(&self) -> &[u8] {
+        &self.buffer[BYTECOUNT / COLOR::BUFFER_COUNT * (COLOR::BUFFER_COUNT - 1)..]
+    }
+
     /// 设置指定像素颜色
     pub fn set_pixel(&mut self, pixel: Pixel<COLOR>) {
         set_pixel(&mut self.buffer, WIDTH, HEIGHT, pixel);
     }
+
+    /// 生成缓冲区文件头，格式见 [`BUFFER_HEADER_LEN`]
+    pub fn header(panel_id: u16) -> [u8; BUFFER_HEADER_LEN] {
+        let mut header = [0; BUFFER_HEADER_LEN];
+        header[..4].copy_from_slice(&BUFFER_MAGIC);
+        header[4..6].copy_from_slice(&(WIDTH as u16).to_le_bytes());
+        header[6..8].copy_from_slice(&(HEIGHT as u16).to_le_bytes());
+        header[8] = pixel_format::<COLOR>();
+        header[9] = BitOrder::NATIVE as u8;
+        header[10..12].copy_from_slice(&panel_id.to_le_bytes());
+        header
+    }
+
+    /// 导出为带文件头的缓冲区文件，通过 `out` 依次输出
+    ///
+    /// `panel_id` 通常为驱动模块的 `PANEL_ID`，载入时用于校验
+    pub fn to_bytes(&self, panel_id: u16, mut out: impl FnMut(&[u8])) {
+        out(&Self::header(panel_id));
+        out(&self.buffer);
+    }
+
+    /// 从 [`to_bytes`](Self::to_bytes) 导出的数据创建显示缓冲区
+    pub fn from_bytes(bytes: &[u8], panel_id: u16) -> Result<Self, BufferError> {
+        let mut display = Self::default();
+        display.load_bytes(bytes, panel_id)?;
+        Ok(display)
+    }
+
+    /// 校验并载入缓冲区文件，位序与当前构建不同时自动转换
+    ///
+    /// 文件或参数中的面板编号为 0 时不校验面板编号；出错时缓冲区保持不变
+    pub fn load_bytes(&mut self, bytes: &[u8], panel_id: u16) -> Result<(), BufferError> {
+        if bytes.len() < BUFFER_HEADER_LEN || bytes[..4] != BUFFER_MAGIC {
+            return Err(BufferError::BadMagic);
+        }
+        let (header, data) = bytes.split_at(BUFFER_HEADER_LEN);
+        let width = u16::from_le_bytes([header[4], header[5]]) as u32;
+        let height = u16::from_le_bytes([header[6], header[7]]) as u32;
+        if width != WIDTH || height != HEIGHT {
+            return Err(BufferError::SizeMismatch);
+        }
+        if header[8] != pixel_format::<COLOR>() {
+            return Err(BufferError::BppMismatch);
+        }
+        let order = match header[9] {
+            0 => BitOrder::MsbFirst,
+            1 => BitOrder::LsbFirst,
+            _ => return Err(BufferError::BadBitOrder),
+        };
+        let file_panel = u16::from_le_bytes([header[10], header[11]]);
+        if file_panel != 0 && panel_id != 0 && file_panel != panel_id {
+            return Err(BufferError::PanelMismatch);
+        }
+        if data.len() != BYTECOUNT {
+            return Err(BufferError::LengthMismatch);
+        }
+
+        self.buffer.copy_from_slice(data);
+        if order != BitOrder::NATIVE {
+            for byte in self.buffer.iter_mut() {
+                *byte = reverse_pixels(*byte, COLOR::BITS_PER_PIXEL);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 文件头中的像素格式：低 4 位为每平面每像素位数，高 4 位为平面数减一
+fn pixel_format<COLOR: ColorType>() -> u8 {
+    (COLOR::BITS_PER_PIXEL | (COLOR::BUFFER_COUNT - 1) << 4) as u8
+}
+
+/// 反转字节内像素的顺序，用于转换位序
+fn reverse_pixels(byte: u8, bits_per_pixel: usize) -> u8 {
+    match bits_per_pixel {
+        1 => byte.reverse_bits(),
+        2 => {
+            let byte = byte.rotate_left(4);
+            (byte & 0x33) << 2 | (byte & 0xCC) >> 2
+        }
+        4 => byte.rotate_left(4),
+        _ => byte,
+    }
+}
+
+impl<const WIDTH: u32, const HEIGHT: u32, const BYTECOUNT: usize>
+    Display<WIDTH, HEIGHT, BYTECOUNT, QuadColor>
+{
+    /// 以 [`PatternColor`] 虚拟颜色绘制
+    pub fn pattern_fill(&mut self) -> PatternFill<'_, Self> {
+        PatternFill::new(self)
+    }
+
+    /// 将图像绘制到指定位置（图像左上角）
+    ///
+    /// x 坐标按 4 像素对齐时整字节复制图像数据，否则逐像素绘制。
+    /// 模拟器使用不同的位序，始终逐像素绘制。
+    pub fn draw_image(&mut self, image: &QuadImage, position: Point) {
+        let area = Rectangle::new(position, image.size()).intersection(&self.bounding_box());
+        if area.is_zero_sized() {
+            return;
+        }
+
+        if cfg!(feature = "simulator") || position.x.rem_euclid(4) != 0 {
+            for point in area.points() {
+                if let Some(color) = image.pixel(point - position) {
+                    self.set_pixel(Pixel(point, color));
+                }
+            }
+            return;
+        }
+
+        let src_line = line_bytes(image.size().width, QuadColor::BITS_PER_PIXEL);
+        let dst_line = line_bytes(WIDTH, QuadColor::BITS_PER_PIXEL);
+        let full = area.size.width as usize / 4;
+        let rest = area.size.width as usize % 4;
+        // 最后一个不完整字节中属于图像的高位
+        let rest_mask = !(0xFFu8 >> (rest * 2));
+        let src_x = (area.top_left.x - position.x) as usize / 4;
+        let dst_x = area.top_left.x as usize / 4;
+
+        for dy in area.rows() {
+            let src = (dy - position.y) as usize * src_line + src_x;
+            let dst = dy as usize * dst_line + dst_x;
+            let data = image.data();
+            self.buffer[dst..dst + full].copy_from_slice(&data[src..src + full]);
+            if rest > 0 {
+                self.buffer[dst + full] =
+                    self.buffer[dst + full] & !rest_mask | data[src + full] & rest_mask;
+            }
+        }
+    }
+}
+
+/// 将其他颜色类型转换后绘制到目标上的适配器
+///
+/// 用于在墨水屏上复用为 TFT 等其他颜色类型编写的控件和字体：
+///
+/// ```ignore
+/// let mut target = display.color_converted::<Rgb565>();
+/// Text::new("Hello", Point::new(10, 20), style_rgb565).draw(&mut target)?;
+/// ```
+pub struct ColorConverted<'a, D, C> {
+    target: &'a mut D,
+    _color: PhantomData<C>,
+}
+
+impl<'a, D, C> ColorConverted<'a, D, C>
+where
+    D: DrawTarget,
+    C: PixelColor + Into<D::Color>,
+{
+    /// 包装绘图目标
+    pub fn new(target: &'a mut D) -> Self {
+        ColorConverted {
+            target,
+            _color: PhantomData,
+        }
+    }
+}
+
+impl<D, C> DrawTarget for ColorConverted<'_, D, C>
+where
+    D: DrawTarget,
+    C: PixelColor + Into<D::Color>,
+{
+    type Color = C;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(point, color)| Pixel(point, color.into())),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.target
+            .fill_contiguous(area, colors.into_iter().map(Into::into))
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.fill_solid(area, color.into())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(color.into())
+    }
+}
+
+impl<D, C> Dimensions for ColorConverted<'_, D, C>
+where
+    D: DrawTarget,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+/// 4x4 有序抖动阈值，用于按比例混合两种颜色
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// 由 4x4 固定图案组成的虚拟颜色
+///
+/// 图案按绝对坐标平铺，相邻图形的图案可以无缝衔接。
+/// 通过 [`PatternFill`] 绘制到四色目标上，可用于 `fill_solid` 及图元样式：
+///
+/// ```ignore
+/// let mut target = display.pattern_fill();
+/// Rectangle::new(Point::new(10, 10), Size::new(40, 100))
+///     .into_styled(PrimitiveStyle::with_fill(PatternColor::ORANGE))
+///     .draw(&mut target)?;
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PatternColor {
+    pattern: [[QuadColor; 4]; 4],
+}
+
+impl PatternColor {
+    /// 橙色：红黄棋盘格
+    pub const ORANGE: Self = Self::checker(QuadColor::Red, QuadColor::Yellow);
+    /// 灰色：黑白棋盘格
+    pub const GRAY: Self = Self::checker(QuadColor::Black, QuadColor::White);
+    /// 浅灰色：25% 黑色
+    pub const LIGHT_GRAY: Self = Self::mix(QuadColor::Black, QuadColor::White, 4);
+    /// 深灰色：75% 黑色
+    pub const DARK_GRAY: Self = Self::mix(QuadColor::Black, QuadColor::White, 12);
+    /// 粉色：红白棋盘格
+    pub const PINK: Self = Self::checker(QuadColor::Red, QuadColor::White);
+    /// 深红色：红黑棋盘格
+    pub const DARK_RED: Self = Self::checker(QuadColor::Red, QuadColor::Black);
+    /// 浅黄色：黄白棋盘格
+    pub const LIGHT_YELLOW: Self = Self::checker(QuadColor::Yellow, QuadColor::White);
+    /// 橄榄色：黄黑棋盘格
+    pub const OLIVE: Self = Self::checker(QuadColor::Yellow, QuadColor::Black);
+
+    /// 从 4x4 图案创建，`pattern[y][x]` 对应坐标对 4 取模后的像素
+    pub const fn new(pattern: [[QuadColor; 4]; 4]) -> Self {
+        PatternColor { pattern }
+    }
+
+    /// 纯色
+    pub const fn solid(color: QuadColor) -> Self {
+        PatternColor {
+            pattern: [[color; 4]; 4],
+        }
+    }
+
+    /// 2x2 棋盘格，左上角为 `a`
+    pub const fn checker(a: QuadColor, b: QuadColor) -> Self {
+        PatternColor {
+            pattern: [[a, b, a, b], [b, a, b, a], [a, b, a, b], [b, a, b, a]],
+        }
+    }
+
+    /// 按 `count / 16` 的比例混合 `a` 与 `b`，像素按有序抖动分布
+    pub const fn mix(a: QuadColor, b: QuadColor, count: u8) -> Self {
+        let mut pattern = [[b; 4]; 4];
+        let mut y = 0;
+        while y < 4 {
+            let mut x = 0;
+            while x < 4 {
+                if BAYER_4X4[y][x] < count {
+                    pattern[y][x] = a;
+                }
+                x += 1;
+            }
+            y += 1;
+        }
+        PatternColor { pattern }
+    }
+
+    /// 指定坐标处的墨水颜色
+    pub fn color_at(&self, point: Point) -> QuadColor {
+        self.pattern[point.y.rem_euclid(4) as usize][point.x.rem_euclid(4) as usize]
+    }
+
+    /// 图案是否为纯色
+    fn as_solid(&self) -> Option<QuadColor> {
+        let first = self.pattern[0][0];
+        self.pattern
+            .iter()
+            .flatten()
+            .all(|&color| color == first)
+            .then_some(first)
+    }
+}
+
+impl PixelColor for PatternColor {
+    type Raw = ();
+}
+
+impl From<QuadColor> for PatternColor {
+    fn from(color: QuadColor) -> Self {
+        PatternColor::solid(color)
+    }
+}
+
+/// 将 [`PatternColor`] 按坐标展开为墨水颜色后绘制到四色目标上的适配器
+pub struct PatternFill<'a, D> {
+    target: &'a mut D,
+}
+
+impl<'a, D> PatternFill<'a, D>
+where
+    D: DrawTarget<Color = QuadColor>,
+{
+    /// 包装绘图目标
+    pub fn new(target: &'a mut D) -> Self {
+        PatternFill { target }
+    }
+}
+
+impl<D> DrawTarget for PatternFill<'_, D>
+where
+    D: DrawTarget<Color = QuadColor>,
+{
+    type Color = PatternColor;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(point, color)| Pixel(point, color.color_at(point))),
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        match color.as_solid() {
+            Some(color) => self.target.fill_solid(area, color),
+            None => self
+                .target
+                .fill_contiguous(area, area.points().map(|point| color.color_at(point))),
+        }
+    }
+}
+
+impl<D> Dimensions for PatternFill<'_, D>
+where
+    D: DrawTarget<Color = QuadColor>,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
 }
 
 /// 设置缓冲区中指定像素的颜色
@@ -116,4 +551,179 @@ fn set_pixel<COLOR: ColorType + PixelColor>(
     let (mask, bits) = color.bitmask(x as u32);
 
     buffer[index] = buffer[index] & mask | bits as u8;
+    if COLOR::BUFFER_COUNT == 2 {
+        let index = index + buffer.len() / 2;
+        buffer[index] = buffer[index] & mask | (bits >> 8) as u8;
+    }
+}
+
+/// 将从第 `first_row` 行开始的打包像素数据绘制到模拟器，字节内最左侧像素位于低位
+#[cfg(feature = "simulator")]
+pub(crate) fn draw_packed<COLOR, D>(
+    target: &mut D,
+    first_row: u32,
+    bytes: impl Iterator<Item = u8>,
+) where
+    COLOR: ColorType + PixelColor,
+    D: DrawTarget<Color = COLOR> + OriginDimensions,
+    D::Error: core::fmt::Debug,
+{
+    let bits = COLOR::BITS_PER_PIXEL;
+    let mask = ((1u16 << bits) - 1) as u8;
+    let width = target.size().width as usize;
+
+    let colors = bytes
+        .flat_map(|byte| (0..8 / bits).map(move |i| COLOR::from_bits(byte >> (i * bits) & mask)));
+
+    let pixels = colors.enumerate().map(|(i, color)| {
+        let x = (i % width) as i32;
+        let y = (first_row as usize + i / width) as i32;
+        Pixel(Point::new(x, y), color)
+    });
+
+    target
+        .draw_iter(pixels)
+        .expect("Failed to draw frame to EPD simulator");
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use embedded_graphics_core::pixelcolor::BinaryColor;
+    use std::vec::Vec;
+
+    type QuadDisplay = Display<8, 2, { crate::buffer_len(8, 2 * 2) }, QuadColor>;
+    type MonoDisplay = Display<8, 2, { crate::buffer_len(8, 2) }, BinaryColor>;
+
+    fn sample() -> QuadDisplay {
+        let mut display = QuadDisplay::default();
+        display.set_pixel(Pixel(Point::new(0, 0), QuadColor::Black));
+        display.set_pixel(Pixel(Point::new(3, 0), QuadColor::Red));
+        display.set_pixel(Pixel(Point::new(5, 1), QuadColor::Yellow));
+        display
+    }
+
+    fn export(display: &QuadDisplay, panel_id: u16) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        display.to_bytes(panel_id, |chunk| bytes.extend_from_slice(chunk));
+        bytes
+    }
+
+    #[test]
+    fn buffer_round_trip() {
+        let display = sample();
+        let bytes = export(&display, 0x0750);
+        assert_eq!(bytes.len(), BUFFER_HEADER_LEN + display.buffer().len());
+
+        let loaded = QuadDisplay::from_bytes(&bytes, 0x0750).unwrap();
+        assert_eq!(loaded.buffer(), display.buffer());
+        // 任一方的面板编号为 0 时不校验
+        assert!(QuadDisplay::from_bytes(&bytes, 0).is_ok());
+        assert!(QuadDisplay::from_bytes(&export(&display, 0), 0x0371).is_ok());
+    }
+
+    #[test]
+    fn buffer_header_is_validated() {
+        let display = sample();
+        let bytes = export(&display, 0x0750);
+        let with = |offset: usize, value: u8| {
+            let mut bytes = bytes.clone();
+            bytes[offset] = value;
+            QuadDisplay::from_bytes(&bytes, 0x0750).err()
+        };
+
+        assert_eq!(with(0, b'X'), Some(BufferError::BadMagic));
+        assert_eq!(
+            QuadDisplay::from_bytes(&bytes[..BUFFER_HEADER_LEN - 1], 0).err(),
+            Some(BufferError::BadMagic)
+        );
+        assert_eq!(with(4, 9), Some(BufferError::SizeMismatch));
+        assert_eq!(with(6, 3), Some(BufferError::SizeMismatch));
+        assert_eq!(with(8, 1), Some(BufferError::BppMismatch));
+        assert_eq!(with(9, 2), Some(BufferError::BadBitOrder));
+        assert_eq!(
+            QuadDisplay::from_bytes(&bytes, 0x0371).err(),
+            Some(BufferError::PanelMismatch)
+        );
+        assert_eq!(
+            QuadDisplay::from_bytes(&bytes[..bytes.len() - 1], 0).err(),
+            Some(BufferError::LengthMismatch)
+        );
+        // 同尺寸但颜色类型不同
+        assert_eq!(
+            MonoDisplay::from_bytes(&bytes, 0).err(),
+            Some(BufferError::BppMismatch)
+        );
+    }
+
+    #[test]
+    fn load_failure_keeps_buffer() {
+        let mut display = sample();
+        let before = display.buffer().to_vec();
+        let mut bytes = export(&QuadDisplay::default(), 0);
+        bytes.pop();
+        assert!(display.load_bytes(&bytes, 0).is_err());
+        assert_eq!(display.buffer(), before);
+    }
+
+    #[test]
+    fn foreign_bit_order_is_converted() {
+        let display = sample();
+        let mut bytes = export(&display, 0);
+        let foreign = match BitOrder::NATIVE {
+            BitOrder::MsbFirst => BitOrder::LsbFirst,
+            BitOrder::LsbFirst => BitOrder::MsbFirst,
+        };
+        bytes[9] = foreign as u8;
+        for byte in &mut bytes[BUFFER_HEADER_LEN..] {
+            *byte = reverse_pixels(*byte, QuadColor::BITS_PER_PIXEL);
+        }
+
+        let loaded = QuadDisplay::from_bytes(&bytes, 0).unwrap();
+        assert_eq!(loaded.buffer(), display.buffer());
+    }
+
+    #[test]
+    fn draw_image_matches_per_pixel_drawing() {
+        type Target = Display<16, 3, { crate::buffer_len(16, 3 * 2) }, QuadColor>;
+
+        // 宽 7 像素，每行 2 字节，末字节的最后一个像素为填充位
+        let data = [0x1B, 0xE5, 0xE4, 0x6F, 0x93, 0x3A];
+        let image = QuadImage::new(&data, 7);
+        for x in -5..=13 {
+            for y in -2..=2 {
+                let position = Point::new(x, y);
+                let background = || {
+                    let mut display = Target::default();
+                    display.buffer.fill(0b10_01_11_00);
+                    display
+                };
+
+                let mut expected = background();
+                for point in expected.bounding_box().points() {
+                    if let Some(color) = image.pixel(point - position) {
+                        expected.set_pixel(Pixel(point, color));
+                    }
+                }
+
+                let mut actual = background();
+                actual.draw_image(&image, position);
+                assert_eq!(actual.buffer(), expected.buffer(), "position {position:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn reverse_pixels_swaps_pixel_order() {
+        assert_eq!(reverse_pixels(0b1000_0001 | 0b0100_0000, 1), 0b1000_0011);
+        assert_eq!(reverse_pixels(0b11_10_01_00, 2), 0b00_01_10_11);
+        assert_eq!(reverse_pixels(0xAB, 4), 0xBA);
+        for bits in [1, 2, 4] {
+            for byte in 0..=u8::MAX {
+                assert_eq!(reverse_pixels(reverse_pixels(byte, bits), bits), byte);
+            }
+        }
+    }
 }
@@ -0,0 +1,144 @@
+//! 打包的四色图像
+
+use crate::color::ColorType;
+use crate::color::QuadColor;
+use embedded_graphics_core::image::ImageDrawable;
+use embedded_graphics_core::prelude::*;
+use embedded_graphics_core::primitives::Rectangle;
+
+/// 嵌入由 `assets::include_image`（需启用 `assets` 特性）在构建期生成的图像
+///
+/// 需要与 `build.rs` 中的调用配对使用，参数为调用时指定的 `name`，
+/// 展开为 `QuadImage<'static>` 常量表达式：
+///
+/// ```ignore
+/// // build.rs（build-dependencies 中启用 `assets` 特性）
+/// assets::include_image("assets/logo.png", "logo", DitherMode::FloydSteinberg).unwrap();
+///
+/// // main.rs
+/// static LOGO: QuadImage<'static> = include_quad_image!("logo");
+/// ```
+///
+/// 宏读取 `$OUT_DIR/<name>.qimg`：名称与 `build.rs` 中不一致，或 crate 没有调用
+/// `include_image` 的 `build.rs` 时，编译会报告 `OUT_DIR` 未定义或找不到该文件。
+/// `include_image` 会为源图像输出 `cargo:rerun-if-changed`，修改图像后自动重新生成
+#[macro_export]
+macro_rules! include_quad_image {
+    ($name:literal) => {
+        $crate::image::QuadImage::from_embedded(include_bytes!(concat!(
+            env!("OUT_DIR"),
+            "/",
+            $name,
+            ".qimg"
+        )))
+    };
+}
+
+/// 2 bpp 打包的四色图像
+///
+/// 数据按行存储，每行补齐到整字节，每字节 4 个像素，
+/// 最左侧像素位于最高两位（与面板 SRAM 的位序相同）
+///
+/// 既可通过 `Image` 绘制到任意 `DrawTarget`，也可用
+/// [`Display::draw_image`](crate::graphics::Display::draw_image) 按整字节快速复制
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuadImage<'a> {
+    data: &'a [u8],
+    width: u32,
+    height: u32,
+}
+
+impl<'a> QuadImage<'a> {
+    /// 嵌入格式的文件头长度：宽、高各为 2 字节小端
+    pub const HEADER_LEN: usize = 4;
+
+    /// 从打包数据创建图像，高度由数据长度推算
+    pub const fn new(data: &'a [u8], width: u32) -> Self {
+        let row_bytes = row_bytes(width);
+        let height = match data.len().checked_div(row_bytes) {
+            Some(height) => height as u32,
+            None => 0,
+        };
+        QuadImage {
+            data,
+            width,
+            height,
+        }
+    }
+
+    /// 从带文件头的嵌入数据创建图像
+    ///
+    /// 文件头与数据长度不符时会 panic（在常量上下文中即为编译错误）
+    pub const fn from_embedded(bytes: &'a [u8]) -> Self {
+        assert!(bytes.len() >= Self::HEADER_LEN, "图像数据缺少文件头");
+        let (header, data) = bytes.split_at(Self::HEADER_LEN);
+        let width = u16::from_le_bytes([header[0], header[1]]) as u32;
+        let height = u16::from_le_bytes([header[2], header[3]]) as u32;
+        assert!(
+            data.len() == row_bytes(width) * height as usize,
+            "图像数据长度与文件头不符"
+        );
+        QuadImage {
+            data,
+            width,
+            height,
+        }
+    }
+
+    /// 获取打包数据
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// 获取指定位置的像素颜色，越界时返回 `None`
+    pub fn pixel(&self, point: Point) -> Option<QuadColor> {
+        if point.x < 0
+            || point.y < 0
+            || point.x as u32 >= self.width
+            || point.y as u32 >= self.height
+        {
+            return None;
+        }
+        let x = point.x as usize;
+        let byte = self.data[point.y as usize * row_bytes(self.width) + x / 4];
+        let shift = 6 - (x % 4) * 2;
+        Some(QuadColor::from_bits((byte >> shift) & 0x03))
+    }
+}
+
+/// 每行字节数
+const fn row_bytes(width: u32) -> usize {
+    (width as usize).div_ceil(4)
+}
+
+impl OriginDimensions for QuadImage<'_> {
+    fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+}
+
+impl ImageDrawable for QuadImage<'_> {
+    type Color = QuadColor;
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = QuadColor>,
+    {
+        self.draw_sub_image(target, &self.bounding_box())
+    }
+
+    fn draw_sub_image<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = QuadColor>,
+    {
+        let area = area.intersection(&self.bounding_box());
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+
+        let colors = area
+            .points()
+            .map(|point| self.pixel(point).unwrap_or_default());
+        target.fill_contiguous(&Rectangle::new(Point::zero(), area.size), colors)
+    }
+}
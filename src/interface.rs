@@ -1,11 +1,102 @@
+use crate::energy::{EnergyMeter, EnergyStats, PowerState};
+use crate::lvd::LvdThreshold;
 use crate::traits::Command;
 
 use core::marker::PhantomData;
 use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal::spi::Operation;
 use embedded_hal_async::{delay::DelayNs, digital::Wait, spi::SpiDevice};
 
+/// 保持片选时单个 SPI 事务最多包含的分块数
+///
+/// 超过后数据拆分为多个事务，片选在事务之间释放
+const MAX_OPERATIONS: usize = 32;
+
+/// 等待 BUSY 拉起时最多读取引脚的次数
+#[cfg(not(feature = "simulator"))]
+const BUSY_ASSERT_POLLS: u32 = 1_000;
+
+/// 重复发送数据时使用的栈缓冲区大小
+const REPEAT_BLOCK_SIZE: usize = 512;
+
+/// 各控制器共用的电源与刷新命令
+#[derive(Copy, Clone)]
+enum CommonCommand {
+    /// 关闭电源
+    PowerOff = 0x02,
+    /// 开启电源
+    PowerOn = 0x04,
+    /// 深度睡眠
+    DeepSleep = 0x07,
+    /// 显示刷新
+    #[cfg(not(feature = "simulator"))]
+    DisplayRefresh = 0x12,
+    /// 低功耗检测
+    #[cfg(not(feature = "simulator"))]
+    LowPowerDetection = 0x51,
+    /// LVD 电压选择
+    LvdVoltageSelect = 0xE4,
+}
+
+impl Command for CommonCommand {
+    fn address(self) -> u8 {
+        self as u8
+    }
+}
+
+/// SPI 数据传输配置
+///
+/// 大块数据（如整帧缓冲区）会按 `chunk_size` 拆分后发送，
+/// 以适应 spidev 缓冲区（默认 4096 字节）或 DMA 描述符长度等限制。
+/// 默认不拆分，由调用方按平台选择，Linux spidev 可使用 [`TransferConfig::SPIDEV`]。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransferConfig {
+    /// 单次 SPI 写入的最大字节数
+    pub chunk_size: usize,
+    /// 分块之间是否保持片选有效
+    ///
+    /// 为 `true` 时，分块作为同一事务中的多个写操作发送；为 `false` 时每个分块单独成为一个事务。
+    ///
+    /// 每个事务最多包含 32 个分块，数据更多时每 32 个分块之间仍会释放一次片选。
+    /// 控制器按 DC 引脚区分命令与数据，片选释放不会中断数据传输。
+    pub keep_cs_asserted: bool,
+}
+
+impl TransferConfig {
+    /// 不拆分，整块数据在一次写入中发送
+    pub const UNLIMITED: Self = TransferConfig {
+        chunk_size: usize::MAX,
+        keep_cs_asserted: true,
+    };
+
+    /// Linux spidev：按 4096 字节分块，每个分块单独提交
+    ///
+    /// spidev 限制的是整个事务的总长度，同一事务中的多个分块无法绕过该限制
+    pub const SPIDEV: Self = TransferConfig {
+        chunk_size: 4096,
+        keep_cs_asserted: false,
+    };
+
+    /// 使用指定分块大小，分块之间保持片选有效
+    pub const fn new(chunk_size: usize) -> Self {
+        TransferConfig {
+            chunk_size,
+            keep_cs_asserted: true,
+        }
+    }
+}
+
+impl Default for TransferConfig {
+    /// 不拆分，与 [`TransferConfig::UNLIMITED`] 相同
+    fn default() -> Self {
+        TransferConfig::UNLIMITED
+    }
+}
+
 /// EPD 设备连接接口
-pub(crate) struct DisplayInterface<SPI, BUSY, DC, RST> {
+///
+/// 同时记录各驱动共用的面板状态，例如升压电路是否上电、功耗统计
+pub(crate) struct DisplayInterface<SPI, BUSY, DC, RST, DELAY> {
     _spi: PhantomData<SPI>,
     /// 低电平表示忙，等待显示就绪
     busy: BUSY,
@@ -13,14 +104,29 @@ pub(crate) struct DisplayInterface<SPI, BUSY, DC, RST> {
     dc: DC,
     /// 复位引脚
     rst: RST,
+    /// SPI 数据传输配置
+    transfer: TransferConfig,
+    /// 升压电路是否已上电，首次复位前未知
+    powered: Option<bool>,
+    /// 刷新完成后自动断电
+    auto_power_off: bool,
+    /// `PowerOff` 命令的参数
+    power_off_data: &'static [u8],
+    /// LVD 阈值，复位后需要重新写入
+    lvd_threshold: LvdThreshold,
+    /// 面板当前的供电状态，未启用功耗统计时也会记录
+    power_state: PowerState,
+    /// 功耗统计，未启用时为 `None`
+    energy: Option<EnergyMeter<DELAY>>,
 }
 
-impl<SPI, BUSY, DC, RST> DisplayInterface<SPI, BUSY, DC, RST>
+impl<SPI, BUSY, DC, RST, DELAY> DisplayInterface<SPI, BUSY, DC, RST, DELAY>
 where
     SPI: SpiDevice,
     BUSY: InputPin + Wait,
     DC: OutputPin,
     RST: OutputPin,
+    DELAY: DelayNs,
 {
     /// 创建新的 DisplayInterface
     pub fn new(busy: BUSY, dc: DC, rst: RST) -> Self {
@@ -29,7 +135,154 @@ where
             busy,
             dc,
             rst,
+            transfer: TransferConfig::default(),
+            powered: None,
+            auto_power_off: false,
+            power_off_data: &[],
+            lvd_threshold: LvdThreshold::default(),
+            power_state: PowerState::default(),
+            energy: None,
+        }
+    }
+
+    /// 设置 SPI 数据传输配置
+    pub(crate) fn set_transfer_config(&mut self, transfer: TransferConfig) {
+        self.transfer = transfer;
+    }
+
+    /// 设置 `PowerOff` 命令的参数，默认不带参数
+    pub(crate) fn set_power_off_data(&mut self, data: &'static [u8]) {
+        self.power_off_data = data;
+    }
+
+    /// 设置刷新完成后是否自动断电
+    pub(crate) fn set_auto_power_off(&mut self, enabled: bool) {
+        self.auto_power_off = enabled;
+    }
+
+    /// 是否在刷新完成后自动断电
+    pub(crate) fn auto_power_off(&self) -> bool {
+        self.auto_power_off
+    }
+
+    /// 升压电路是否处于上电状态
+    pub(crate) fn is_powered(&self) -> bool {
+        self.powered == Some(true)
+    }
+
+    /// 为升压电路上电，已上电时不重复发送命令
+    pub(crate) async fn power_on(
+        &mut self,
+        spi: &mut SPI,
+        is_busy_low: bool,
+    ) -> Result<(), SPI::Error> {
+        if self.powered != Some(true) {
+            self.enter_power_state(PowerState::On);
+            self.cmd(spi, CommonCommand::PowerOn).await?;
+            #[cfg(not(feature = "simulator"))]
+            self.wait_until_busy(is_busy_low).await;
+            self.wait_until_idle(is_busy_low).await;
+            self.powered = Some(true);
+        }
+        Ok(())
+    }
+
+    /// 等待刷新完成后为升压电路断电，已断电时不重复发送命令
+    ///
+    /// 首次复位前无法得知面板是否仍处于上电状态，此时总是发送 `PowerOff`
+    pub(crate) async fn power_off(
+        &mut self,
+        spi: &mut SPI,
+        is_busy_low: bool,
+    ) -> Result<(), SPI::Error> {
+        if self.powered != Some(false) {
+            self.wait_until_idle(is_busy_low).await;
+            self.cmd(spi, CommonCommand::PowerOff).await?;
+            if !self.power_off_data.is_empty() {
+                self.data(spi, self.power_off_data).await?;
+            }
+            self.wait_until_idle(is_busy_low).await;
+            self.powered = Some(false);
+            self.enter_power_state(PowerState::Off);
+        }
+        Ok(())
+    }
+
+    /// 断电后进入深度睡眠，之后需要复位才能唤醒
+    pub(crate) async fn deep_sleep(
+        &mut self,
+        spi: &mut SPI,
+        is_busy_low: bool,
+    ) -> Result<(), SPI::Error> {
+        self.power_off(spi, is_busy_low).await?;
+        self.cmd_with_data(spi, CommonCommand::DeepSleep, &[0xA5])
+            .await?;
+        self.enter_power_state(PowerState::DeepSleep);
+        Ok(())
+    }
+
+    /// 设置 LVD 阈值并写入控制器
+    pub(crate) async fn set_lvd_threshold(
+        &mut self,
+        spi: &mut SPI,
+        is_busy_low: bool,
+        threshold: LvdThreshold,
+    ) -> Result<(), SPI::Error> {
+        self.lvd_threshold = threshold;
+        self.wait_until_idle(is_busy_low).await;
+        self.cmd_with_data(spi, CommonCommand::LvdVoltageSelect, &[threshold as u8])
+            .await
+    }
+
+    /// 当前 LVD 阈值
+    pub(crate) fn lvd_threshold(&self) -> LvdThreshold {
+        self.lvd_threshold
+    }
+
+    /// 读取低电压标志，供电电压低于 LVD 阈值时返回 `true`
+    #[cfg(not(feature = "simulator"))]
+    pub(crate) async fn is_low_voltage(
+        &mut self,
+        spi: &mut SPI,
+        is_busy_low: bool,
+    ) -> Result<bool, SPI::Error> {
+        self.wait_until_idle(is_busy_low).await;
+        let mut flag = [0];
+        self.cmd_read(spi, CommonCommand::LowPowerDetection, &mut flag)
+            .await?;
+        Ok(flag[0] & 0x01 == 0)
+    }
+
+    /// 必要时上电，发送刷新命令并等待 BUSY 拉起，`data` 为刷新命令的参数
+    #[cfg(not(feature = "simulator"))]
+    pub(crate) async fn start_refresh(
+        &mut self,
+        spi: &mut SPI,
+        is_busy_low: bool,
+        data: &[u8],
+    ) -> Result<(), SPI::Error> {
+        self.power_on(spi, is_busy_low).await?;
+        self.cmd(spi, CommonCommand::DisplayRefresh).await?;
+        if !data.is_empty() {
+            self.data(spi, data).await?;
+        }
+        self.enter_power_state(PowerState::Refreshing);
+        self.wait_until_busy(is_busy_low).await;
+        Ok(())
+    }
+
+    /// 等待刷新完成，开启自动断电时随后断电
+    #[cfg(not(feature = "simulator"))]
+    pub(crate) async fn finish_refresh(
+        &mut self,
+        spi: &mut SPI,
+        is_busy_low: bool,
+    ) -> Result<(), SPI::Error> {
+        self.wait_until_idle(is_busy_low).await;
+        if self.auto_power_off {
+            self.power_off(spi, is_busy_low).await?;
         }
+        Ok(())
     }
 
     /// 发送命令
@@ -62,60 +315,247 @@ where
         self.data(spi, data).await
     }
 
+    /// 发送命令后读取数据
+    ///
+    /// 控制器通过 SDA 线输出数据，需要 SPI 总线支持在数据线上读取
+    #[cfg(not(feature = "simulator"))]
+    pub(crate) async fn cmd_read<T: Command>(
+        &mut self,
+        spi: &mut SPI,
+        command: T,
+        buf: &mut [u8],
+    ) -> Result<(), SPI::Error> {
+        self.cmd(spi, command).await?;
+        let _ = self.dc.set_high();
+        spi.read(buf).await
+    }
+
     /// 发送重复字节
     pub(crate) async fn data_x_times(
         &mut self,
         spi: &mut SPI,
         val: u8,
         repetitions: u32,
+    ) -> Result<(), SPI::Error> {
+        self.data_pattern(spi, &[val], repetitions as usize).await
+    }
+
+    /// 循环发送字节图案，共发送 `len` 字节
+    ///
+    /// 图案先在栈上展开为数据块，再以尽可能少的事务重复发送该数据块
+    pub(crate) async fn data_pattern(
+        &mut self,
+        spi: &mut SPI,
+        pattern: &[u8],
+        len: usize,
     ) -> Result<(), SPI::Error> {
         let _ = self.dc.set_high();
-        for _ in 0..repetitions {
-            self.write(spi, &[val]).await?;
+        if pattern.is_empty() || len == 0 {
+            return Ok(());
+        }
+
+        // 数据块长度取图案长度的整数倍，保证各块首尾相接时图案连续
+        let capacity = REPEAT_BLOCK_SIZE.min(self.transfer.chunk_size.max(1));
+        let mut block = [0u8; REPEAT_BLOCK_SIZE];
+        let unit = if pattern.len() <= capacity {
+            let unit_len = capacity / pattern.len() * pattern.len();
+            for (byte, value) in block[..unit_len].iter_mut().zip(pattern.iter().cycle()) {
+                *byte = *value;
+            }
+            &block[..unit_len]
+        } else {
+            pattern
+        };
+
+        self.write_repeated(spi, unit, len / unit.len()).await?;
+        self.write(spi, &unit[..len % unit.len()]).await
+    }
+
+    /// 将同一数据块连续发送 `count` 次
+    async fn write_repeated(
+        &mut self,
+        spi: &mut SPI,
+        unit: &[u8],
+        count: usize,
+    ) -> Result<(), SPI::Error> {
+        if cfg!(feature = "simulator") {
+            return Ok(());
+        }
+
+        if !self.transfer.keep_cs_asserted || unit.len() > self.transfer.chunk_size {
+            for _ in 0..count {
+                self.write(spi, unit).await?;
+            }
+            return Ok(());
+        }
+
+        let mut remaining = count;
+        while remaining > 0 {
+            let batch = remaining.min(MAX_OPERATIONS);
+            let mut operations: [Operation<'_, u8>; MAX_OPERATIONS] =
+                core::array::from_fn(|_| Operation::Write(unit));
+            spi.transaction(&mut operations[..batch]).await?;
+            remaining -= batch;
         }
         Ok(())
     }
 
     /// SPI 写入辅助函数
     async fn write(&mut self, spi: &mut SPI, data: &[u8]) -> Result<(), SPI::Error> {
-        if cfg!(target_os = "linux") {
-            for data_chunk in data.chunks(4096) {
-                spi.write(data_chunk).await?;
+        if cfg!(feature = "simulator") {
+            return Ok(());
+        }
+
+        if data.len() <= self.transfer.chunk_size {
+            return spi.write(data).await;
+        }
+
+        let mut chunks = data.chunks(self.transfer.chunk_size.max(1));
+        if !self.transfer.keep_cs_asserted {
+            for chunk in chunks {
+                spi.write(chunk).await?;
             }
-            Ok(())
-        } else if cfg!(feature = "simulator") {
-            Ok(())
-        } else {
-            spi.write(data).await
+            return Ok(());
+        }
+
+        loop {
+            let mut operations: [Operation<'_, u8>; MAX_OPERATIONS] =
+                core::array::from_fn(|_| Operation::Write(&[]));
+            let mut count = 0;
+            for (operation, chunk) in operations.iter_mut().zip(&mut chunks) {
+                *operation = Operation::Write(chunk);
+                count += 1;
+            }
+            if count == 0 {
+                return Ok(());
+            }
+            spi.transaction(&mut operations[..count]).await?;
         }
     }
 
     /// 等待设备空闲（busy == HIGH）
+    ///
+    /// 启用功耗统计时改为按固定间隔轮询，等待时间计入当前状态
     pub(crate) async fn wait_until_idle(&mut self, is_busy_low: bool) {
-        match is_busy_low {
-            true => {
-                let _ = self.busy.wait_for_high().await;
+        if let Some(meter) = &mut self.energy {
+            let busy = &mut self.busy;
+            meter
+                .wait_until_idle(|| busy_level(busy, is_busy_low))
+                .await;
+        } else {
+            match is_busy_low {
+                true => {
+                    let _ = self.busy.wait_for_high().await;
+                }
+                false => {
+                    let _ = self.busy.wait_for_low().await;
+                }
             }
-            false => {
-                let _ = self.busy.wait_for_low().await;
+        }
+        self.idle();
+    }
+
+    /// 等待设备进入忙状态，最多轮询 [`BUSY_ASSERT_POLLS`] 次
+    ///
+    /// 刷新命令发出后 BUSY 引脚需要一段时间才会拉起，用于避免随后的查询误读为空闲。
+    /// 忙状态可能在轮询前就已结束，因此不使用边沿等待，超过次数后直接返回
+    #[cfg(not(feature = "simulator"))]
+    pub(crate) async fn wait_until_busy(&mut self, is_busy_low: bool) {
+        for _ in 0..BUSY_ASSERT_POLLS {
+            if busy_level(&mut self.busy, is_busy_low) {
+                return;
             }
         }
     }
 
+    /// 设备是否忙，读取引脚失败时视为空闲
+    ///
+    /// 空闲时结束复位或刷新状态
+    pub(crate) fn is_busy(&mut self, is_busy_low: bool) -> bool {
+        let busy = busy_level(&mut self.busy, is_busy_low);
+        if !busy {
+            self.idle();
+        }
+        busy
+    }
+
+    /// 观察到 BUSY 空闲时结束复位或刷新状态
+    fn idle(&mut self) {
+        match self.power_state {
+            PowerState::Reset => self.enter_power_state(PowerState::Off),
+            PowerState::Refreshing => self.enter_power_state(PowerState::On),
+            _ => {}
+        }
+    }
+
     /// 复位设备
     ///
-    /// 复位引脚保持低电平的时间对不同设备很重要
+    /// 复位引脚保持低电平的时间对不同设备很重要。启用功耗统计时由统计器执行延时，
+    /// 延时计入复位状态
     pub(crate) async fn reset<D: DelayNs>(
         &mut self,
         delay: &mut D,
         initial_delay: u32,
         duration: u32,
     ) {
+        self.enter_power_state(PowerState::Reset);
+        self.powered = Some(false);
         let _ = self.rst.set_high();
-        delay.delay_us(initial_delay).await;
+        self.delay_us(delay, initial_delay).await;
         let _ = self.rst.set_low();
-        delay.delay_us(duration).await;
+        self.delay_us(delay, duration).await;
         let _ = self.rst.set_high();
-        delay.delay_us(200_000).await;
+        self.delay_us(delay, 200_000).await;
+    }
+
+    /// 延时 `us` 微秒，启用功耗统计时改用统计器的延时并计时
+    async fn delay_us<D: DelayNs>(&mut self, delay: &mut D, us: u32) {
+        match &mut self.energy {
+            Some(meter) => meter.delay_us(us).await,
+            None => delay.delay_us(us).await,
+        }
+    }
+
+    /// 启用功耗统计，从当前状态开始计时
+    pub(crate) fn enable_energy_meter(&mut self, delay: DELAY) {
+        self.energy = Some(EnergyMeter::new(delay, self.power_state));
+    }
+
+    /// 停用功耗统计
+    pub(crate) fn disable_energy_meter(&mut self) {
+        self.energy = None;
+    }
+
+    /// 截至当前的功耗统计
+    pub(crate) fn energy_stats(&self) -> Option<EnergyStats> {
+        self.energy.as_ref().map(EnergyMeter::stats)
+    }
+
+    /// 取出截至当前的功耗统计并清零
+    pub(crate) fn take_energy_stats(&mut self) -> Option<EnergyStats> {
+        self.energy.as_mut().map(EnergyMeter::take)
+    }
+
+    /// 将 `us` 微秒计入当前状态
+    pub(crate) fn add_elapsed_time(&mut self, us: u64) {
+        if let Some(meter) = &mut self.energy {
+            meter.add(us);
+        }
+    }
+
+    /// 切换面板的供电状态，之后的时间计入该状态
+    pub(crate) fn enter_power_state(&mut self, state: PowerState) {
+        self.power_state = state;
+        if let Some(meter) = &mut self.energy {
+            meter.enter(state);
+        }
+    }
+}
+
+/// 读取 BUSY 引脚，读取失败时视为空闲
+fn busy_level<BUSY: InputPin>(busy: &mut BUSY, is_busy_low: bool) -> bool {
+    match is_busy_low {
+        true => busy.is_low().unwrap_or(false),
+        false => busy.is_high().unwrap_or(false),
     }
 }
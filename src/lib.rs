@@ -5,29 +5,69 @@
 //!
 //! [`embedded-graphics`]: https://docs.rs/embedded-graphics/
 //! [`embedded-hal`]: https://docs.rs/embedded-hal
-#![cfg_attr(not(feature = "simulator"), no_std)]
+#![no_std]
 #![deny(missing_docs)]
 
+#[macro_use]
+mod macros;
+
 #[cfg(feature = "graphics")]
 pub mod graphics;
 
+#[cfg(feature = "graphics")]
+pub mod dither;
+
+#[cfg(feature = "graphics")]
+pub mod image;
+
+#[cfg(any(feature = "bmp", feature = "png"))]
+pub mod decode;
+
+#[cfg(feature = "assets")]
+pub mod assets;
+
 mod traits;
 
 pub mod color;
 
+pub mod rle;
+
+pub mod lut;
+
+pub mod refresh;
+
+pub mod energy;
+
+pub mod lvd;
+
+#[cfg(feature = "mock")]
+pub mod mock;
+
+#[cfg(feature = "linux")]
+pub mod linux;
+
 /// 显示与控制设备之间的物理连接接口
 mod interface;
 
+pub use interface::TransferConfig;
+
 pub mod gdew0371w7;
+#[cfg(feature = "graphics")]
+pub mod gdew075t7;
+pub mod gdew075z08;
+pub mod gdey073d46;
 pub mod yrd0750ryf665f60;
 
 /// 包含除选定显示类型外的所有重要内容
 pub mod prelude {
     pub use crate::color::QuadColor;
+    pub use crate::refresh::{RefreshControl, RefreshMode};
     pub use crate::traits::WaveshareDisplay;
 
     #[cfg(feature = "graphics")]
     pub use crate::graphics::Display;
+    #[cfg(feature = "graphics")]
+    pub use crate::image::QuadImage;
 }
 
 /// 计算所需的缓冲区长度。处理宽度不能被 8 整除时的向上取整
@@ -39,5 +79,5 @@ pub mod prelude {
 /// \[XXXXX210\]\[76543210\]...\[76543210\] | 高度
 /// \[XXXXX210\]\[76543210\]...\[76543210\] v
 pub const fn buffer_len(width: usize, height: usize) -> usize {
-    (width + 7) / 8 * height
+    width.div_ceil(8) * height
 }
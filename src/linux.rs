@@ -0,0 +1,204 @@
+//! 基于 spidev 与 gpio-cdev 的 Linux 后端
+//!
+//! [`linux-embedded-hal`] 只提供阻塞式 `embedded-hal` 实现，
+//! 此模块用 [`Blocking`] 将其适配为驱动所需的异步 trait，
+//! 并提供 [`LinuxConfig::open`] 一次性打开 SPI 与控制引脚。
+//!
+//! 默认引脚与 Waveshare 树莓派 HAT 一致（BCM 编号）。
+//! spidev 默认限制单次传输 4096 字节，创建驱动后需要通过 `set_transfer_config`
+//! 设置 [`TransferConfig::SPIDEV`](crate::TransferConfig::SPIDEV)。
+//!
+//! [`linux-embedded-hal`]: https://docs.rs/linux-embedded-hal
+
+extern crate std;
+
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal::spi::Operation;
+use linux_embedded_hal::gpio_cdev::{Chip, LineRequestFlags};
+use linux_embedded_hal::spidev::{SpiModeFlags, SpidevOptions};
+use linux_embedded_hal::{CdevPin, Delay, SpidevDevice};
+
+/// 申请 GPIO 时使用的 consumer 名称
+const CONSUMER: &str = "epd";
+
+/// 将阻塞式 `embedded-hal` 实现适配为异步 trait
+///
+/// 所有操作都会阻塞当前线程；BUSY 等待通过轮询实现。
+pub struct Blocking<T> {
+    inner: T,
+    poll_interval: Duration,
+}
+
+impl<T> Blocking<T> {
+    /// 包装阻塞式外设，BUSY 轮询间隔默认 1 ms
+    pub fn new(inner: T) -> Self {
+        Blocking {
+            inner,
+            poll_interval: Duration::from_millis(1),
+        }
+    }
+
+    /// 设置 BUSY 轮询间隔
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// 取回被包装的外设
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    fn poll_until<E>(&mut self, mut done: impl FnMut(&mut T) -> Result<bool, E>) -> Result<(), E> {
+        while !done(&mut self.inner)? {
+            std::thread::sleep(self.poll_interval);
+        }
+        Ok(())
+    }
+}
+
+impl<T: embedded_hal::spi::ErrorType> embedded_hal::spi::ErrorType for Blocking<T> {
+    type Error = T::Error;
+}
+
+impl<T: embedded_hal::spi::SpiDevice> embedded_hal_async::spi::SpiDevice for Blocking<T> {
+    async fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), T::Error> {
+        self.inner.transaction(operations)
+    }
+}
+
+impl<T: embedded_hal::delay::DelayNs> embedded_hal_async::delay::DelayNs for Blocking<T> {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.inner.delay_ns(ns);
+    }
+}
+
+impl<T: embedded_hal::digital::ErrorType> embedded_hal::digital::ErrorType for Blocking<T> {
+    type Error = T::Error;
+}
+
+impl<T: InputPin> InputPin for Blocking<T> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.inner.is_high()
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.inner.is_low()
+    }
+}
+
+impl<T: OutputPin> OutputPin for Blocking<T> {
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.inner.set_high()
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.inner.set_low()
+    }
+}
+
+impl<T: InputPin> embedded_hal_async::digital::Wait for Blocking<T> {
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        self.poll_until(|pin| pin.is_high())
+    }
+
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        self.poll_until(|pin| pin.is_low())
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        self.poll_until(|pin| pin.is_low())?;
+        self.poll_until(|pin| pin.is_high())
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        self.poll_until(|pin| pin.is_high())?;
+        self.poll_until(|pin| pin.is_low())
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        let high = self.inner.is_high()?;
+        self.poll_until(|pin| pin.is_high().map(|level| level != high))
+    }
+}
+
+/// Linux 设备路径与引脚配置
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinuxConfig {
+    /// spidev 设备路径
+    pub spi_path: PathBuf,
+    /// SPI 时钟频率（Hz）
+    pub spi_hz: u32,
+    /// GPIO 字符设备路径
+    pub gpiochip: PathBuf,
+    /// BUSY 引脚线号
+    pub busy: u32,
+    /// DC 引脚线号
+    pub dc: u32,
+    /// RST 引脚线号
+    pub rst: u32,
+}
+
+impl Default for LinuxConfig {
+    fn default() -> Self {
+        LinuxConfig {
+            spi_path: PathBuf::from("/dev/spidev0.0"),
+            spi_hz: 4_000_000,
+            gpiochip: PathBuf::from("/dev/gpiochip0"),
+            busy: 24,
+            dc: 25,
+            rst: 17,
+        }
+    }
+}
+
+/// 已打开的 Linux 外设
+pub struct LinuxPeripherals {
+    /// SPI 设备（片选由内核控制）
+    pub spi: Blocking<SpidevDevice>,
+    /// BUSY 输入引脚
+    pub busy: Blocking<CdevPin>,
+    /// DC 输出引脚
+    pub dc: CdevPin,
+    /// RST 输出引脚
+    pub rst: CdevPin,
+    /// 延时
+    pub delay: Blocking<Delay>,
+}
+
+impl LinuxConfig {
+    /// 打开 SPI 设备并申请 GPIO
+    pub fn open(&self) -> io::Result<LinuxPeripherals> {
+        let mut spi = SpidevDevice::open(&self.spi_path).map_err(io::Error::other)?;
+        spi.configure(
+            &SpidevOptions::new()
+                .bits_per_word(8)
+                .max_speed_hz(self.spi_hz)
+                .mode(SpiModeFlags::SPI_MODE_0)
+                .build(),
+        )?;
+
+        let mut chip = Chip::new(&self.gpiochip).map_err(io::Error::other)?;
+        let mut request = |line: u32, flags: LineRequestFlags, default: u8| {
+            chip.get_line(line)
+                .and_then(|line| line.request(flags, default, CONSUMER))
+                .and_then(CdevPin::new)
+                .map_err(io::Error::other)
+        };
+        let busy = request(self.busy, LineRequestFlags::INPUT, 0)?;
+        let dc = request(self.dc, LineRequestFlags::OUTPUT, 1)?;
+        let rst = request(self.rst, LineRequestFlags::OUTPUT, 1)?;
+
+        Ok(LinuxPeripherals {
+            spi: Blocking::new(spi),
+            busy: Blocking::new(busy),
+            dc,
+            rst,
+            delay: Blocking::new(Delay),
+        })
+    }
+}
@@ -0,0 +1,50 @@
+//! 自定义波形（LUT）
+//!
+//! UC81xx 系列控制器默认使用 OTP 中的波形，`PanelSetting` 的 REG 位置 1 后改用
+//! 寄存器中的波形。驱动的 `set_lut` 上传一组波形表并切换到寄存器波形，
+//! `set_lut_source(LutSource::Otp)` 切换回 OTP 波形。
+//!
+//! 复位（包括 `wake_up`）后寄存器波形丢失，控制器恢复为 OTP 波形。
+//!
+//! 仅 UC8179 驱动（`gdew075t7`、`gdew075z08`）支持上传波形；四色与七色面板的控制器
+//! 没有公开的波形寄存器格式，只使用 OTP 波形。本 crate 不附带波形表，也不提供灰度模式，
+//! 波形表需由调用者提供。错误的波形可能损伤面板，请仅使用面板厂商提供或验证过的波形。
+
+/// UC81xx 波形寄存器
+///
+/// 黑白模式下依次为 VCOM 及各像素转换（旧 → 新）的波形；
+/// 三色模式下 `Bw`、`Wb`、`Bb` 分别用作红色、白色、黑色波形
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LutRegister {
+    /// VCOM 波形
+    Vcom = 0x20,
+    /// 白 → 白
+    Ww = 0x21,
+    /// 黑 → 白
+    Bw = 0x22,
+    /// 白 → 黑
+    Wb = 0x23,
+    /// 黑 → 黑
+    Bb = 0x24,
+    /// 边框
+    Border = 0x25,
+}
+
+impl crate::traits::Command for LutRegister {
+    fn address(self) -> u8 {
+        self as u8
+    }
+}
+
+/// 一组波形表，依次上传到各寄存器
+pub type Lut<'a> = [(LutRegister, &'a [u8])];
+
+/// 波形来源
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum LutSource {
+    /// OTP 中的出厂波形
+    #[default]
+    Otp,
+    /// 通过 `set_lut` 上传的寄存器波形
+    Register,
+}
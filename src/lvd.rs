@@ -0,0 +1,46 @@
+//! 低电压检测（LVD）
+//!
+//! UC81xx 控制器在供电电压低于 LVD 阈值时清除 `LowPowerDetection` 标志。
+//! 电压不足时刷新的颜色（尤其是红色、黄色）会明显偏淡，电池供电的设备应在刷新前检查。
+//!
+//! 读取标志需要控制器通过 SDA 线输出数据，SPI 总线须支持在数据线上读取（三线或双向连接）。
+
+use core::fmt;
+
+/// LVD 阈值，复位后为 [`LvdThreshold::V2_5`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum LvdThreshold {
+    /// 低于 2.2 V
+    V2_2 = 0b00,
+    /// 低于 2.3 V
+    V2_3 = 0b01,
+    /// 低于 2.4 V
+    V2_4 = 0b10,
+    /// 低于 2.5 V
+    #[default]
+    V2_5 = 0b11,
+}
+
+/// 带电压检查的刷新错误
+#[derive(Debug, PartialEq, Eq)]
+pub enum RefreshError<E> {
+    /// SPI 通信失败
+    Spi(E),
+    /// 供电电压低于 LVD 阈值，未刷新
+    LowVoltage,
+}
+
+impl<E> From<E> for RefreshError<E> {
+    fn from(e: E) -> Self {
+        RefreshError::Spi(e)
+    }
+}
+
+impl<E: fmt::Debug> fmt::Display for RefreshError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RefreshError::Spi(e) => write!(f, "SPI 通信失败: {:?}", e),
+            RefreshError::LowVoltage => f.write_str("供电电压过低，无法可靠刷新"),
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! 驱动共用方法的生成宏
+//!
+//! 驱动的状态由 `DisplayInterface` 记录，宏在驱动的 inherent impl 中展开为转发方法，
+//! 要求驱动含有 `interface` 字段、泛型参数命名为 `SPI, BUSY, DC, RST, DELAY`，
+//! 并在模块中定义 `IS_BUSY_LOW`。
+
+/// SPI 传输配置与升压电路的电源管理
+macro_rules! impl_power_methods {
+    () => {
+        /// 设置 SPI 数据传输的分块方式
+        ///
+        /// 默认不分块，见 [`TransferConfig::default`](crate::TransferConfig::default)
+        pub fn set_transfer_config(&mut self, config: $crate::TransferConfig) {
+            self.interface.set_transfer_config(config);
+        }
+
+        /// 设置刷新完成后是否自动断电
+        ///
+        /// 开启后 `display_frame` 在刷新完成后发送 PowerOff，下一次刷新前再自动上电，
+        /// 可降低两次刷新之间的电流。使用 `start_refresh` 时需要在刷新完成后自行调用 [`power_off`](Self::power_off)
+        pub fn set_auto_power_off(&mut self, enabled: bool) {
+            self.interface.set_auto_power_off(enabled);
+        }
+
+        /// 是否在刷新完成后自动断电
+        pub fn auto_power_off(&self) -> bool {
+            self.interface.auto_power_off()
+        }
+
+        /// 升压电路是否处于上电状态
+        pub fn is_powered(&self) -> bool {
+            self.interface.is_powered()
+        }
+
+        /// 为升压电路上电，已上电时不重复发送命令
+        pub async fn power_on(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+            self.interface.power_on(spi, IS_BUSY_LOW).await
+        }
+
+        /// 等待刷新完成后为升压电路断电，SRAM 中的帧数据保留；已断电时不重复发送命令
+        pub async fn power_off(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+            self.interface.power_off(spi, IS_BUSY_LOW).await
+        }
+    };
+}
+
+/// 低电压检测，见 [`lvd`](crate::lvd)
+///
+/// 驱动需要在初始化时通过 `set_lvd_threshold` 重新写入阈值
+macro_rules! impl_lvd_methods {
+    () => {
+        /// 设置 LVD 阈值，复位后由 `wake_up` 重新写入
+        pub async fn set_lvd_threshold(
+            &mut self,
+            spi: &mut SPI,
+            threshold: $crate::lvd::LvdThreshold,
+        ) -> Result<(), SPI::Error> {
+            self.interface
+                .set_lvd_threshold(spi, IS_BUSY_LOW, threshold)
+                .await
+        }
+
+        /// 当前 LVD 阈值
+        pub fn lvd_threshold(&self) -> $crate::lvd::LvdThreshold {
+            self.interface.lvd_threshold()
+        }
+
+        /// 读取低电压标志，供电电压低于 LVD 阈值时返回 `true`
+        #[cfg(not(feature = "simulator"))]
+        pub async fn is_low_voltage(&mut self, spi: &mut SPI) -> Result<bool, SPI::Error> {
+            self.interface.is_low_voltage(spi, IS_BUSY_LOW).await
+        }
+
+        /// 读取低电压标志，模拟器中始终为 `false`
+        #[cfg(feature = "simulator")]
+        pub async fn is_low_voltage(&mut self, _spi: &mut SPI) -> Result<bool, SPI::Error> {
+            Ok(false)
+        }
+
+        /// 上电后检查供电电压，电压正常时显示 SRAM 中的帧数据
+        ///
+        /// 电压低于 LVD 阈值时返回 [`RefreshError::LowVoltage`](crate::lvd::RefreshError::LowVoltage)，
+        /// 帧数据仍保留在 SRAM 中，可以稍后重试，或直接调用 `display_frame` 强制刷新
+        pub async fn display_frame_checked(
+            &mut self,
+            spi: &mut SPI,
+        ) -> Result<(), $crate::lvd::RefreshError<SPI::Error>> {
+            self.power_on(spi).await?;
+            if self.is_low_voltage(spi).await? {
+                if self.auto_power_off() {
+                    self.power_off(spi).await?;
+                }
+                return Err($crate::lvd::RefreshError::LowVoltage);
+            }
+            Ok(self.display_frame(spi).await?)
+        }
+    };
+}
+
+/// 单平面面板的整帧图案填充与流式传输
+///
+/// `$command` 为开始传输帧数据的命令，模块中需要定义 `ROW_BYTES`、`FRAME_BYTES` 与 `HEIGHT`
+macro_rules! impl_frame_methods {
+    ($color:ty, $command:expr) => {
+        /// 用循环重复的字节图案填充整帧 SRAM，不触发刷新
+        ///
+        /// 图案按缓冲区的打包格式解释，长度为行字节数整数倍的图案得到横条纹，可用于消除残影
+        #[cfg(not(feature = "simulator"))]
+        pub async fn update_frame_pattern(
+            &mut self,
+            spi: &mut SPI,
+            pattern: &[u8],
+        ) -> Result<(), SPI::Error> {
+            self.wait_until_idle().await?;
+            self.interface.cmd(spi, $command).await?;
+            self.interface.data_pattern(spi, pattern, FRAME_BYTES).await
+        }
+
+        /// 用循环重复的字节图案填充整帧 SRAM，不触发刷新
+        #[cfg(feature = "simulator")]
+        pub async fn update_frame_pattern(
+            &mut self,
+            _spi: &mut SPI,
+            pattern: &[u8],
+        ) -> Result<(), SPI::Error> {
+            self.draw_simulator(0, pattern.iter().copied().cycle().take(FRAME_BYTES));
+            Ok(())
+        }
+
+        /// 逐行生成并传输整帧数据，无需完整的帧缓冲区
+        ///
+        /// `fill_row(y, row)` 依次以行号 `0..HEIGHT` 调用，`row` 为一行的打包像素数据，
+        /// 调用前已填充为白色
+        #[cfg(not(feature = "simulator"))]
+        pub async fn update_frame_from_rows<F>(
+            &mut self,
+            spi: &mut SPI,
+            mut fill_row: F,
+        ) -> Result<(), SPI::Error>
+        where
+            F: FnMut(u32, &mut [u8]),
+        {
+            self.wait_until_idle().await?;
+            self.interface.cmd(spi, $command).await?;
+            let mut row = [0u8; ROW_BYTES];
+            for y in 0..HEIGHT {
+                row.fill(<$color as $crate::color::ColorType>::DEFAULT_COLOR_BYTE);
+                fill_row(y, &mut row);
+                self.interface.data(spi, &row).await?;
+            }
+            Ok(())
+        }
+
+        /// 逐行生成并传输整帧数据，无需完整的帧缓冲区
+        #[cfg(feature = "simulator")]
+        pub async fn update_frame_from_rows<F>(
+            &mut self,
+            _spi: &mut SPI,
+            mut fill_row: F,
+        ) -> Result<(), SPI::Error>
+        where
+            F: FnMut(u32, &mut [u8]),
+        {
+            let mut row = [0u8; ROW_BYTES];
+            for y in 0..HEIGHT {
+                row.fill(<$color as $crate::color::ColorType>::DEFAULT_COLOR_BYTE);
+                fill_row(y, &mut row);
+                self.draw_simulator(y, row.iter().copied());
+            }
+            Ok(())
+        }
+
+        /// 从字节迭代器流式传输整帧数据
+        ///
+        /// 字节按缓冲区的打包格式解释；不足一帧时剩余部分为白色，多余的字节被忽略
+        pub async fn update_frame_from_iter<I>(
+            &mut self,
+            spi: &mut SPI,
+            bytes: I,
+        ) -> Result<(), SPI::Error>
+        where
+            I: IntoIterator<Item = u8>,
+        {
+            let mut bytes = bytes.into_iter();
+            self.update_frame_from_rows(spi, |_, row| {
+                for (byte, value) in row.iter_mut().zip(&mut bytes) {
+                    *byte = value;
+                }
+            })
+            .await
+        }
+
+        /// 将从第 `first_row` 行开始的打包像素数据绘制到模拟器
+        #[cfg(feature = "simulator")]
+        fn draw_simulator(&mut self, first_row: u32, bytes: impl Iterator<Item = u8>) {
+            $crate::graphics::draw_packed(&mut self.simulator_display, first_row, bytes);
+        }
+    };
+}
+
+/// 纯色刷新、存放前清屏与残影消除
+///
+/// `$colors` 为消除残影时依次显示的颜色，驱动需要提供 `clear_frame_with`
+macro_rules! impl_color_methods {
+    ($color:ty, $white:expr, $colors:expr) => {
+        /// 将整帧显示为纯色并等待刷新完成
+        pub async fn display_color(
+            &mut self,
+            spi: &mut SPI,
+            color: $color,
+        ) -> Result<(), SPI::Error> {
+            self.clear_frame_with(spi, color).await?;
+            self.display_frame(spi).await
+        }
+
+        /// 长期存放或运输前调用：清为白色后断电并进入深度睡眠
+        ///
+        /// 长时间保持同一画面容易留下烙印，厂商建议存放前清为白色。之后需要 `wake_up` 才能继续使用
+        pub async fn prepare_for_storage(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+            self.display_color(spi, $white).await?;
+            self.sleep(spi).await
+        }
+
+        /// 消除长时间显示同一画面留下的残影
+        ///
+        /// 依次显示面板支持的每种颜色并重复 `cycles` 次，最后停留在白色；每个颜色都是一次完整刷新
+        pub async fn recover_image_retention(
+            &mut self,
+            spi: &mut SPI,
+            cycles: u8,
+        ) -> Result<(), SPI::Error> {
+            for _ in 0..cycles {
+                for color in $colors {
+                    self.display_color(spi, color).await?;
+                }
+            }
+            self.display_color(spi, $white).await
+        }
+    };
+}
+
+/// 功耗统计，见 [`energy`](crate::energy)
+macro_rules! impl_energy_methods {
+    () => {
+        /// 启用功耗统计，`delay` 用于执行复位延时，并在等待 BUSY 时按 1 ms 间隔轮询计时
+        ///
+        /// 统计从调用时开始，按复位、断电、上电、刷新、深度睡眠分别累计时间；
+        /// 使用 `start_refresh` 时，只有通过 `wait_until_idle` 等待的刷新时间会被计入
+        pub fn enable_energy_meter(&mut self, delay: DELAY) {
+            self.interface.enable_energy_meter(delay);
+        }
+
+        /// 停用功耗统计
+        pub fn disable_energy_meter(&mut self) {
+            self.interface.disable_energy_meter();
+        }
+
+        /// 截至当前的功耗统计，未启用时返回 `None`
+        pub fn energy_stats(&self) -> Option<$crate::energy::EnergyStats> {
+            self.interface.energy_stats()
+        }
+
+        /// 取出截至当前的功耗统计并清零，可在每次刷新后调用以获得单次刷新的数据
+        pub fn take_energy_stats(&mut self) -> Option<$crate::energy::EnergyStats> {
+            self.interface.take_energy_stats()
+        }
+
+        /// 将驱动无法测量的时间计入当前状态，例如两次刷新之间处于断电或深度睡眠的时间
+        pub fn add_elapsed_time(&mut self, us: u64) {
+            self.interface.add_elapsed_time(us);
+        }
+    };
+}
@@ -0,0 +1,763 @@
+//! 用于主机端测试的模拟硬件
+//!
+//! [`MockPanel`] 模拟 UC81xx 系列控制器的 BUSY 时序与电源状态，
+//! 并记录驱动违反通信协议的行为，例如：
+//! - BUSY 期间写入命令或数据
+//! - 未执行 `PowerOn` 就发送 `DisplayRefresh`
+//! - `DeepSleep` 之后未复位就继续发送命令
+//! - 升压电路仍上电时进入 `DeepSleep`
+//!
+//! 所有延时均基于虚拟时钟，不会真正阻塞。
+//!
+//! 注意：启用 `simulator` 特性时驱动不会写入 SPI，此时只有 BUSY 时序生效。
+
+extern crate std;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::vec::Vec;
+
+use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal::spi::{ErrorKind, Operation};
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+use embedded_hal_async::spi::SpiDevice;
+
+const CMD_POWER_OFF: u8 = 0x02;
+const CMD_POWER_ON: u8 = 0x04;
+const CMD_DEEP_SLEEP: u8 = 0x07;
+const CMD_DISPLAY_REFRESH: u8 = 0x12;
+const CMD_LOW_POWER_DETECTION: u8 = 0x51;
+const DEEP_SLEEP_CHECK_CODE: u8 = 0xA5;
+
+/// 模拟面板各操作的忙碌时长（微秒）
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MockTiming {
+    /// 复位后的忙碌时长
+    pub reset_us: u32,
+    /// `PowerOn` 后的忙碌时长
+    pub power_on_us: u32,
+    /// `PowerOff` 后的忙碌时长
+    pub power_off_us: u32,
+    /// `DisplayRefresh` 后的忙碌时长
+    pub refresh_us: u32,
+}
+
+impl Default for MockTiming {
+    fn default() -> Self {
+        MockTiming {
+            reset_us: 10_000,
+            power_on_us: 100_000,
+            power_off_us: 50_000,
+            refresh_us: 15_000_000,
+        }
+    }
+}
+
+/// 检测到的协议违规
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Violation {
+    /// BUSY 期间写入（`command` 为正在发送的命令，数据阶段为所属命令）
+    WriteWhileBusy {
+        /// 相关命令
+        command: Option<u8>,
+    },
+    /// 在发送任何命令之前写入数据
+    DataWithoutCommand,
+    /// 深度睡眠后未经复位写入
+    WriteAfterDeepSleep {
+        /// 相关命令
+        command: Option<u8>,
+    },
+    /// 未上电就刷新显示
+    RefreshWithoutPowerOn,
+    /// 未发送 `PowerOff` 就进入深度睡眠
+    DeepSleepWhilePowered,
+    /// 等待的 BUSY 电平永远不会出现（真实硬件上会挂起）
+    WaitWouldHang,
+}
+
+/// 严格模式下 SPI 返回的错误
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MockError(pub Violation);
+
+impl embedded_hal::spi::Error for MockError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+/// 模拟控制器内部状态
+#[derive(Debug, Default)]
+struct State {
+    timing: MockTiming,
+    busy_low: bool,
+    strict: bool,
+    /// 虚拟时钟（纳秒）
+    now_ns: u64,
+    busy_until_ns: u64,
+    dc_high: bool,
+    rst_low: bool,
+    powered: bool,
+    asleep: bool,
+    /// 供电电压低于 LVD 阈值
+    low_voltage: bool,
+    last_command: Option<u8>,
+    /// 当前命令（及其数据）是否被拒绝
+    rejected: bool,
+    commands: Vec<u8>,
+    /// 每条已接受命令之后写入的数据字节数，与 `commands` 一一对应
+    data_lens: Vec<usize>,
+    /// 每个 SPI 事务中各写操作的字节数
+    transactions: Vec<Vec<usize>>,
+    violations: Vec<Violation>,
+}
+
+impl State {
+    fn is_busy(&self) -> bool {
+        self.now_ns < self.busy_until_ns
+    }
+
+    fn set_busy_for(&mut self, us: u32) {
+        self.busy_until_ns = self.now_ns + us as u64 * 1_000;
+    }
+
+    fn advance(&mut self, ns: u64) {
+        self.now_ns += ns;
+    }
+
+    fn busy_level_high(&self) -> bool {
+        self.is_busy() != self.busy_low
+    }
+
+    fn wait_for_level(&mut self, high: bool) {
+        if self.busy_level_high() == high {
+            return;
+        }
+        if self.is_busy() {
+            self.now_ns = self.busy_until_ns;
+        } else {
+            self.violations.push(Violation::WaitWouldHang);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.powered = false;
+        self.asleep = false;
+        self.last_command = None;
+        self.rejected = false;
+        self.set_busy_for(self.timing.reset_us);
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Option<Violation> {
+        let mut first = None;
+        for &byte in bytes {
+            let violation = if self.dc_high {
+                self.write_data(byte)
+            } else {
+                self.write_command(byte)
+            };
+            if let Some(violation) = violation {
+                self.violations.push(violation);
+                first.get_or_insert(violation);
+            }
+        }
+        first
+    }
+
+    fn write_command(&mut self, command: u8) -> Option<Violation> {
+        self.last_command = Some(command);
+        self.rejected = true;
+        if self.asleep {
+            return Some(Violation::WriteAfterDeepSleep {
+                command: Some(command),
+            });
+        }
+        if self.is_busy() {
+            return Some(Violation::WriteWhileBusy {
+                command: Some(command),
+            });
+        }
+        self.rejected = false;
+        self.commands.push(command);
+        self.data_lens.push(0);
+        match command {
+            CMD_POWER_ON => {
+                self.powered = true;
+                self.set_busy_for(self.timing.power_on_us);
+            }
+            CMD_POWER_OFF => {
+                self.powered = false;
+                self.set_busy_for(self.timing.power_off_us);
+            }
+            CMD_DISPLAY_REFRESH => {
+                if !self.powered {
+                    return Some(Violation::RefreshWithoutPowerOn);
+                }
+                self.set_busy_for(self.timing.refresh_us);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn write_data(&mut self, byte: u8) -> Option<Violation> {
+        let command = self.last_command;
+        // 所属命令已被拒绝时，其后的数据不再重复报告
+        if self.rejected {
+            return None;
+        }
+        let violation = if self.asleep {
+            Some(Violation::WriteAfterDeepSleep { command })
+        } else if command.is_none() {
+            Some(Violation::DataWithoutCommand)
+        } else if self.is_busy() && !matches!(command, Some(CMD_DISPLAY_REFRESH | CMD_POWER_OFF)) {
+            // 刷新与断电命令的可选参数字节在 BUSY 拉起前送达
+            Some(Violation::WriteWhileBusy { command })
+        } else {
+            None
+        };
+        if violation.is_some() {
+            self.rejected = true;
+            return violation;
+        }
+        if let Some(len) = self.data_lens.last_mut() {
+            *len += 1;
+        }
+        if command == Some(CMD_DEEP_SLEEP) && byte == DEEP_SLEEP_CHECK_CODE {
+            let violation = self.powered.then_some(Violation::DeepSleepWhilePowered);
+            self.powered = false;
+            self.asleep = true;
+            return violation;
+        }
+        None
+    }
+}
+
+/// 模拟面板，负责创建共享同一状态的模拟 SPI、引脚与延时
+#[derive(Clone, Debug)]
+pub struct MockPanel {
+    state: Rc<RefCell<State>>,
+}
+
+impl Default for MockPanel {
+    fn default() -> Self {
+        Self::new(MockTiming::default())
+    }
+}
+
+impl MockPanel {
+    /// 使用给定时序创建模拟面板（BUSY 低电平表示忙）
+    pub fn new(timing: MockTiming) -> Self {
+        MockPanel {
+            state: Rc::new(RefCell::new(State {
+                timing,
+                busy_low: true,
+                rst_low: false,
+                ..Default::default()
+            })),
+        }
+    }
+
+    /// 设置 BUSY 极性
+    pub fn busy_low(self, busy_low: bool) -> Self {
+        self.state.borrow_mut().busy_low = busy_low;
+        self
+    }
+
+    /// 严格模式：检测到违规时 SPI 写入返回 [`MockError`]
+    pub fn strict(self, strict: bool) -> Self {
+        self.state.borrow_mut().strict = strict;
+        self
+    }
+
+    /// 模拟 SPI 设备
+    pub fn spi(&self) -> MockSpi {
+        MockSpi {
+            state: self.state.clone(),
+        }
+    }
+
+    /// 模拟 BUSY 引脚
+    pub fn busy(&self) -> MockBusy {
+        MockBusy {
+            state: self.state.clone(),
+        }
+    }
+
+    /// 模拟 DC 引脚
+    pub fn dc(&self) -> MockDc {
+        MockDc {
+            state: self.state.clone(),
+        }
+    }
+
+    /// 模拟 RST 引脚
+    pub fn rst(&self) -> MockRst {
+        MockRst {
+            state: self.state.clone(),
+        }
+    }
+
+    /// 基于虚拟时钟的延时
+    pub fn delay(&self) -> MockDelay {
+        MockDelay {
+            state: self.state.clone(),
+        }
+    }
+
+    /// 已记录的协议违规
+    pub fn violations(&self) -> Vec<Violation> {
+        self.state.borrow().violations.clone()
+    }
+
+    /// 清空已记录的违规
+    pub fn clear_violations(&self) {
+        self.state.borrow_mut().violations.clear();
+    }
+
+    /// 已被控制器接受的命令序列
+    pub fn commands(&self) -> Vec<u8> {
+        self.state.borrow().commands.clone()
+    }
+
+    /// 已被控制器接受的命令及其后写入的数据字节数
+    pub fn transfers(&self) -> Vec<(u8, usize)> {
+        let state = self.state.borrow();
+        state
+            .commands
+            .iter()
+            .copied()
+            .zip(state.data_lens.iter().copied())
+            .collect()
+    }
+
+    /// 已发送的 SPI 事务，每项为事务中各写操作的字节数
+    pub fn transactions(&self) -> Vec<Vec<usize>> {
+        self.state.borrow().transactions.clone()
+    }
+
+    /// 当前虚拟时间（微秒）
+    pub fn now_us(&self) -> u64 {
+        self.state.borrow().now_ns / 1_000
+    }
+
+    /// 控制器当前是否忙
+    pub fn is_busy(&self) -> bool {
+        self.state.borrow().is_busy()
+    }
+
+    /// 电荷泵是否已开启
+    pub fn is_powered(&self) -> bool {
+        self.state.borrow().powered
+    }
+
+    /// 是否处于深度睡眠
+    pub fn is_asleep(&self) -> bool {
+        self.state.borrow().asleep
+    }
+
+    /// 模拟供电电压低于 LVD 阈值
+    pub fn set_low_voltage(&self, low: bool) {
+        self.state.borrow_mut().low_voltage = low;
+    }
+}
+
+/// 模拟 SPI 设备
+#[derive(Debug)]
+pub struct MockSpi {
+    state: Rc<RefCell<State>>,
+}
+
+impl embedded_hal::spi::ErrorType for MockSpi {
+    type Error = MockError;
+}
+
+impl SpiDevice for MockSpi {
+    async fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), MockError> {
+        let mut state = self.state.borrow_mut();
+        let writes = operations
+            .iter()
+            .filter_map(|operation| match operation {
+                Operation::Write(bytes) => Some(bytes.len()),
+                Operation::Transfer(_, bytes) => Some(bytes.len()),
+                Operation::TransferInPlace(bytes) => Some(bytes.len()),
+                _ => None,
+            })
+            .collect();
+        state.transactions.push(writes);
+        for operation in operations {
+            let violation = match operation {
+                Operation::Write(bytes) => state.write(bytes),
+                Operation::Transfer(read, write) => {
+                    read.fill(0);
+                    state.write(write)
+                }
+                Operation::TransferInPlace(bytes) => {
+                    let violation = state.write(bytes);
+                    bytes.fill(0);
+                    violation
+                }
+                Operation::Read(bytes) => {
+                    // 低电压标志：1 表示电压正常
+                    let flag =
+                        state.last_command == Some(CMD_LOW_POWER_DETECTION) && !state.low_voltage;
+                    bytes.fill(flag as u8);
+                    None
+                }
+                Operation::DelayNs(ns) => {
+                    state.advance(*ns as u64);
+                    None
+                }
+            };
+            if let (Some(violation), true) = (violation, state.strict) {
+                return Err(MockError(violation));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 模拟 BUSY 引脚
+#[derive(Debug)]
+pub struct MockBusy {
+    state: Rc<RefCell<State>>,
+}
+
+impl embedded_hal::digital::ErrorType for MockBusy {
+    type Error = core::convert::Infallible;
+}
+
+impl InputPin for MockBusy {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.state.borrow().busy_level_high())
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.state.borrow().busy_level_high())
+    }
+}
+
+impl Wait for MockBusy {
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        self.state.borrow_mut().wait_for_level(true);
+        Ok(())
+    }
+
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        self.state.borrow_mut().wait_for_level(false);
+        Ok(())
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        self.state.borrow_mut().wait_for_level(true);
+        Ok(())
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        self.state.borrow_mut().wait_for_level(false);
+        Ok(())
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        let mut state = self.state.borrow_mut();
+        let high = !state.busy_level_high();
+        state.wait_for_level(high);
+        Ok(())
+    }
+}
+
+/// 模拟 DC 引脚
+#[derive(Debug)]
+pub struct MockDc {
+    state: Rc<RefCell<State>>,
+}
+
+impl embedded_hal::digital::ErrorType for MockDc {
+    type Error = core::convert::Infallible;
+}
+
+impl OutputPin for MockDc {
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.state.borrow_mut().dc_high = true;
+        Ok(())
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.state.borrow_mut().dc_high = false;
+        Ok(())
+    }
+}
+
+/// 模拟 RST 引脚，低电平后恢复高电平即视为一次硬件复位
+#[derive(Debug)]
+pub struct MockRst {
+    state: Rc<RefCell<State>>,
+}
+
+impl embedded_hal::digital::ErrorType for MockRst {
+    type Error = core::convert::Infallible;
+}
+
+impl OutputPin for MockRst {
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        let mut state = self.state.borrow_mut();
+        if state.rst_low {
+            state.rst_low = false;
+            state.reset();
+        }
+        Ok(())
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.state.borrow_mut().rst_low = true;
+        Ok(())
+    }
+}
+
+/// 推进虚拟时钟的延时
+#[derive(Debug)]
+pub struct MockDelay {
+    state: Rc<RefCell<State>>,
+}
+
+impl DelayNs for MockDelay {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.state.borrow_mut().advance(ns as u64);
+    }
+}
+
+#[cfg(all(test, not(feature = "simulator")))]
+mod tests {
+    use super::*;
+
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+    use std::vec;
+
+    use crate::TransferConfig;
+    use crate::prelude::WaveshareDisplay;
+    use crate::{gdew075z08, gdew0371w7, gdey073d46, yrd0750ryf665f60};
+
+    /// 模拟硬件从不挂起，用空唤醒器轮询即可
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    type Yrd = yrd0750ryf665f60::Epd7in5<MockSpi, MockBusy, MockDc, MockRst, MockDelay>;
+    type Gdew0371w7 = gdew0371w7::Epd7in5<MockSpi, MockBusy, MockDc, MockRst, MockDelay>;
+    #[cfg(feature = "graphics")]
+    type Gdew075t7 = crate::gdew075t7::Epd7in5<MockSpi, MockBusy, MockDc, MockRst, MockDelay>;
+    type Gdew075z08 = gdew075z08::Epd7in5<MockSpi, MockBusy, MockDc, MockRst, MockDelay>;
+    type Gdey073d46 = gdey073d46::Epd7in3f<MockSpi, MockBusy, MockDc, MockRst, MockDelay>;
+
+    /// 创建驱动并依次初始化、传输、刷新、进入深度睡眠
+    async fn full_cycle<EPD>(panel: &MockPanel, frame_bytes: usize) -> EPD
+    where
+        EPD: WaveshareDisplay<MockSpi, MockBusy, MockDc, MockRst, MockDelay>,
+    {
+        let (mut spi, mut delay) = (panel.spi(), panel.delay());
+        let mut epd = EPD::new(&mut spi, panel.busy(), panel.dc(), panel.rst(), &mut delay)
+            .await
+            .unwrap();
+        epd.wake_up(&mut spi, &mut delay).await.unwrap();
+        epd.update_frame(&mut spi, &vec![0x55; frame_bytes])
+            .await
+            .unwrap();
+        epd.display_frame(&mut spi).await.unwrap();
+        epd.sleep(&mut spi).await.unwrap();
+        epd
+    }
+
+    #[test]
+    fn yrd0750ryf665f60_full_cycle() {
+        let panel = MockPanel::default();
+        let frame_bytes = (yrd0750ryf665f60::WIDTH / 4 * yrd0750ryf665f60::HEIGHT) as usize;
+        block_on(full_cycle::<Yrd>(&panel, frame_bytes));
+        assert_eq!(panel.violations(), []);
+        assert!(panel.is_asleep());
+        assert!(!panel.is_powered());
+    }
+
+    #[test]
+    fn gdew0371w7_full_cycle() {
+        let panel = MockPanel::default();
+        let frame_bytes = (gdew0371w7::WIDTH / 4 * gdew0371w7::HEIGHT) as usize;
+        block_on(full_cycle::<Gdew0371w7>(&panel, frame_bytes));
+        assert_eq!(panel.violations(), []);
+        assert!(panel.is_asleep());
+        assert!(!panel.is_powered());
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn gdew075t7_full_cycle() {
+        use crate::gdew075t7::{HEIGHT, WIDTH};
+
+        let panel = MockPanel::default();
+        let frame_bytes = (WIDTH / 8 * HEIGHT) as usize;
+        block_on(full_cycle::<Gdew075t7>(&panel, frame_bytes));
+        assert_eq!(panel.violations(), []);
+        assert!(panel.is_asleep());
+        assert!(!panel.is_powered());
+        assert!(panel.transfers().contains(&(0x13, frame_bytes)));
+    }
+
+    #[test]
+    fn gdew075z08_sends_both_planes() {
+        use gdew075z08::{HEIGHT, WIDTH};
+
+        let panel = MockPanel::default();
+        let plane_bytes = (WIDTH / 8 * HEIGHT) as usize;
+        block_on(full_cycle::<Gdew075z08>(&panel, plane_bytes * 2));
+        assert_eq!(panel.violations(), []);
+        assert!(panel.is_asleep());
+
+        let planes: Vec<_> = panel
+            .transfers()
+            .into_iter()
+            .filter(|&(command, _)| command == 0x10 || command == 0x13)
+            .collect();
+        assert_eq!(planes, [(0x10, plane_bytes), (0x13, plane_bytes)]);
+    }
+
+    #[test]
+    fn gdey073d46_full_cycle_powers_off_once() {
+        use gdey073d46::{HEIGHT, WIDTH};
+
+        let panel = MockPanel::default();
+        let frame_bytes = (WIDTH / 2 * HEIGHT) as usize;
+        block_on(full_cycle::<Gdey073d46>(&panel, frame_bytes));
+        assert_eq!(panel.violations(), []);
+        assert!(panel.is_asleep());
+        assert!(panel.transfers().contains(&(0x10, frame_bytes)));
+
+        // display_frame 已在刷新后断电，sleep 不再重复发送 PowerOff
+        let commands = panel.commands();
+        assert_eq!(commands.iter().filter(|&&c| c == CMD_POWER_OFF).count(), 1);
+        assert!(commands.ends_with(&[CMD_DISPLAY_REFRESH, CMD_POWER_OFF, CMD_DEEP_SLEEP]));
+    }
+
+    #[test]
+    fn frame_is_split_by_transfer_config() {
+        let frame_bytes = (yrd0750ryf665f60::WIDTH / 4 * yrd0750ryf665f60::HEIGHT) as usize;
+        let frame_transactions = |config: TransferConfig| {
+            let panel = MockPanel::default();
+            let (mut spi, mut delay) = (panel.spi(), panel.delay());
+            block_on(async {
+                let mut epd = Yrd::new(&mut spi, panel.busy(), panel.dc(), panel.rst(), &mut delay)
+                    .await
+                    .unwrap();
+                epd.set_transfer_config(config);
+                let start = panel.transactions().len();
+                epd.update_frame(&mut spi, &vec![0x55; frame_bytes])
+                    .await
+                    .unwrap();
+                assert_eq!(panel.violations(), []);
+                assert_eq!(panel.transfers().last(), Some(&(0x10, frame_bytes)));
+                // 跳过传输命令本身
+                panel.transactions().split_off(start + 1)
+            })
+        };
+
+        assert_eq!(
+            frame_transactions(TransferConfig::UNLIMITED),
+            [vec![frame_bytes]]
+        );
+
+        // 96000 = 13 × 7000 + 5000
+        let mut chunks = vec![7000; 13];
+        chunks.push(5000);
+        assert_eq!(
+            frame_transactions(TransferConfig::new(7000)),
+            [chunks.clone()]
+        );
+        let separate = TransferConfig {
+            chunk_size: 7000,
+            keep_cs_asserted: false,
+        };
+        let expected: Vec<_> = chunks.iter().map(|&len| vec![len]).collect();
+        assert_eq!(frame_transactions(separate), expected);
+
+        // 每个事务最多 32 个分块
+        assert_eq!(
+            frame_transactions(TransferConfig::new(1000)),
+            [vec![1000; 32], vec![1000; 32], vec![1000; 32]]
+        );
+    }
+
+    #[test]
+    fn sleep_powers_off_without_wake_up() {
+        let panel = MockPanel::default();
+        let (mut spi, mut delay) = (panel.spi(), panel.delay());
+        let mut epd = block_on(Yrd::new(
+            &mut spi,
+            panel.busy(),
+            panel.dc(),
+            panel.rst(),
+            &mut delay,
+        ))
+        .unwrap();
+        block_on(epd.wake_up(&mut spi, &mut delay)).unwrap();
+        assert!(panel.is_powered());
+
+        // 例如 MCU 重启后重新创建驱动，面板仍处于上电状态
+        let mut epd = block_on(Yrd::new(
+            &mut spi,
+            panel.busy(),
+            panel.dc(),
+            panel.rst(),
+            &mut delay,
+        ))
+        .unwrap();
+        block_on(epd.sleep(&mut spi)).unwrap();
+        assert_eq!(panel.violations(), []);
+        assert!(!panel.is_powered());
+    }
+
+    #[test]
+    fn energy_meter_times_refresh() {
+        let panel = MockPanel::default();
+        let (mut spi, mut delay) = (panel.spi(), panel.delay());
+        let mut epd = block_on(Yrd::new(
+            &mut spi,
+            panel.busy(),
+            panel.dc(),
+            panel.rst(),
+            &mut delay,
+        ))
+        .unwrap();
+        epd.enable_energy_meter(panel.delay());
+        block_on(epd.wake_up(&mut spi, &mut delay)).unwrap();
+        block_on(epd.display_frame(&mut spi)).unwrap();
+
+        let stats = epd.take_energy_stats().unwrap();
+        let refresh_us = MockTiming::default().refresh_us as u64;
+        assert!(stats.refresh_us.abs_diff(refresh_us) <= 1_000);
+        // 复位前后的延时由统计器执行：20 ms + 20 ms + 200 ms
+        assert_eq!(stats.reset_us, 240_000);
+        assert_eq!(panel.violations(), []);
+    }
+
+    #[test]
+    fn deep_sleep_while_powered_is_flagged() {
+        let panel = MockPanel::default();
+        let (mut spi, mut dc, mut delay) = (panel.spi(), panel.dc(), panel.delay());
+        block_on(async {
+            dc.set_low().unwrap();
+            spi.write(&[CMD_POWER_ON]).await.unwrap();
+            delay.delay_ms(200).await;
+            spi.write(&[CMD_DEEP_SLEEP]).await.unwrap();
+            dc.set_high().unwrap();
+            spi.write(&[DEEP_SLEEP_CHECK_CODE]).await.unwrap();
+        });
+        assert_eq!(panel.violations(), [Violation::DeepSleepWhilePowered]);
+        assert!(panel.is_asleep());
+    }
+}
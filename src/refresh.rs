@@ -0,0 +1,447 @@
+//! 刷新方式与残影管理
+#![allow(async_fn_in_trait)]
+
+use embedded_hal_async::spi::SpiDevice;
+
+/// 刷新方式
+///
+/// 快速刷新使用更短的波形，速度更快但会累积残影，
+/// 建议每隔若干次快速刷新做一次完整刷新
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum RefreshMode {
+    /// 完整刷新，使用出厂波形驱动全部颜色
+    #[default]
+    Full,
+    /// 仅黑白的快速刷新，只适合黑白内容
+    ///
+    /// 需要驱动支持，不支持的驱动按完整刷新处理
+    FastMono,
+}
+
+/// 可由 [`RefreshManager`] 管理的驱动
+pub trait RefreshControl<SPI: SpiDevice> {
+    /// 设置后续刷新使用的方式，返回驱动实际采用的方式
+    ///
+    /// 不支持的方式按完整刷新处理并返回 [`RefreshMode::Full`]
+    fn set_refresh_mode(&mut self, mode: RefreshMode) -> RefreshMode;
+
+    /// 传输并显示整帧，等待刷新完成
+    async fn display_buffer(&mut self, spi: &mut SPI, buffer: &[u8]) -> Result<(), SPI::Error>;
+
+    /// 将整帧显示为纯黑或纯白，等待刷新完成
+    async fn display_solid(&mut self, spi: &mut SPI, black: bool) -> Result<(), SPI::Error>;
+}
+
+/// 完整刷新的调度策略
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RefreshPolicy {
+    /// 两次完整刷新之间最多允许的快速刷新次数
+    pub max_fast_refreshes: u32,
+    /// 距上次完整刷新超过该时长（毫秒）后强制完整刷新
+    pub max_interval_ms: u64,
+    /// 强制完整刷新前先依次显示白、黑、白以清除残影
+    pub clean_sequence: bool,
+    /// 两次刷新之间的最小间隔（毫秒），为 0 时不限制
+    ///
+    /// 对快速刷新同样生效；彩色面板的完整刷新通常建议间隔不少于 180 秒
+    pub min_interval_ms: u64,
+}
+
+impl Default for RefreshPolicy {
+    /// 每 10 次快速刷新或每 24 小时做一次完整刷新，不使用清屏序列，不限制刷新间隔
+    fn default() -> Self {
+        RefreshPolicy {
+            max_fast_refreshes: 10,
+            max_interval_ms: 24 * 60 * 60 * 1000,
+            clean_sequence: false,
+            min_interval_ms: 0,
+        }
+    }
+}
+
+/// 统计快速刷新次数和时间，按策略自动插入完整刷新的刷新管理器
+///
+/// 同时限制最小刷新间隔并统计累计刷新次数，避免应用逻辑异常时频繁刷新损耗面板。
+///
+/// 时间由调用者以单调递增的毫秒数传入，例如 `embassy_time::Instant::now().as_millis()`：
+///
+/// ```ignore
+/// let mut manager = RefreshManager::new(epd, RefreshPolicy::default());
+/// manager
+///     .update_and_display_frame(&mut spi, display.buffer(), RefreshMode::FastMono, now_ms)
+///     .await?;
+/// // 周期性调用，显示被推迟的最新一帧
+/// manager.flush(&mut spi, display.buffer(), now_ms).await?;
+/// ```
+pub struct RefreshManager<EPD> {
+    epd: EPD,
+    policy: RefreshPolicy,
+    /// 上次完整刷新以来的快速刷新次数
+    fast_refreshes: u32,
+    /// 上次完整刷新的时间
+    last_full_ms: Option<u64>,
+    /// 上次刷新的时间
+    last_refresh_ms: Option<u64>,
+    /// 累计刷新次数
+    refresh_count: u64,
+    /// 被推迟的刷新方式
+    pending: Option<RefreshMode>,
+}
+
+impl<EPD> RefreshManager<EPD> {
+    /// 包装驱动，第一次刷新总是完整刷新
+    pub fn new(epd: EPD, policy: RefreshPolicy) -> Self {
+        RefreshManager {
+            epd,
+            policy,
+            fast_refreshes: 0,
+            last_full_ms: None,
+            last_refresh_ms: None,
+            refresh_count: 0,
+            pending: None,
+        }
+    }
+
+    /// 获取驱动引用
+    pub fn epd(&self) -> &EPD {
+        &self.epd
+    }
+
+    /// 获取驱动可变引用，绕过管理器的刷新不会被统计
+    pub fn epd_mut(&mut self) -> &mut EPD {
+        &mut self.epd
+    }
+
+    /// 取回驱动
+    pub fn into_inner(self) -> EPD {
+        self.epd
+    }
+
+    /// 当前策略
+    pub fn policy(&self) -> &RefreshPolicy {
+        &self.policy
+    }
+
+    /// 修改策略
+    pub fn set_policy(&mut self, policy: RefreshPolicy) {
+        self.policy = policy;
+    }
+
+    /// 上次完整刷新以来的快速刷新次数
+    pub fn fast_refreshes(&self) -> u32 {
+        self.fast_refreshes
+    }
+
+    /// 累计刷新次数，包括清屏序列中的刷新
+    pub fn refresh_count(&self) -> u64 {
+        self.refresh_count
+    }
+
+    /// 恢复持久化保存的累计刷新次数
+    pub fn set_refresh_count(&mut self, count: u64) {
+        self.refresh_count = count;
+    }
+
+    /// 是否有因最小间隔被推迟的刷新
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// 允许下一次刷新的最早时间，从未刷新过时返回 `None`
+    pub fn next_refresh_ms(&self) -> Option<u64> {
+        self.last_refresh_ms
+            .map(|last| last.saturating_add(self.policy.min_interval_ms))
+    }
+
+    /// 按策略判断下一次刷新是否必须为完整刷新
+    pub fn needs_full_refresh(&self, now_ms: u64) -> bool {
+        match self.last_full_ms {
+            None => true,
+            Some(last) => {
+                self.fast_refreshes >= self.policy.max_fast_refreshes
+                    || now_ms.saturating_sub(last) >= self.policy.max_interval_ms
+            }
+        }
+    }
+
+    /// 以请求的方式刷新，策略要求时改为完整刷新，返回实际使用的方式
+    ///
+    /// 距上次刷新不足最小间隔时不刷新，记录为待刷新并返回 `None`；
+    /// 之后调用 [`flush`](Self::flush) 显示届时最新的一帧，中间的请求被合并
+    pub async fn update_and_display_frame<SPI>(
+        &mut self,
+        spi: &mut SPI,
+        buffer: &[u8],
+        mode: RefreshMode,
+        now_ms: u64,
+    ) -> Result<Option<RefreshMode>, SPI::Error>
+    where
+        SPI: SpiDevice,
+        EPD: RefreshControl<SPI>,
+    {
+        // 合并的请求中只要有一次要求完整刷新，就按完整刷新处理
+        let mode = match self.pending {
+            Some(RefreshMode::Full) => RefreshMode::Full,
+            _ => mode,
+        };
+        if self.next_refresh_ms().is_some_and(|next| now_ms < next) {
+            self.pending = Some(mode);
+            return Ok(None);
+        }
+        self.refresh(spi, buffer, mode, now_ms).await.map(Some)
+    }
+
+    /// 显示被推迟的刷新，`buffer` 为最新的一帧；没有待刷新或仍未到最小间隔时返回 `None`
+    pub async fn flush<SPI>(
+        &mut self,
+        spi: &mut SPI,
+        buffer: &[u8],
+        now_ms: u64,
+    ) -> Result<Option<RefreshMode>, SPI::Error>
+    where
+        SPI: SpiDevice,
+        EPD: RefreshControl<SPI>,
+    {
+        match self.pending {
+            Some(mode) if self.next_refresh_ms().is_none_or(|next| now_ms >= next) => {
+                self.refresh(spi, buffer, mode, now_ms).await.map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn refresh<SPI>(
+        &mut self,
+        spi: &mut SPI,
+        buffer: &[u8],
+        mode: RefreshMode,
+        now_ms: u64,
+    ) -> Result<RefreshMode, SPI::Error>
+    where
+        SPI: SpiDevice,
+        EPD: RefreshControl<SPI>,
+    {
+        self.pending = None;
+        let mode = if self.needs_full_refresh(now_ms) {
+            RefreshMode::Full
+        } else {
+            mode
+        };
+
+        if mode == RefreshMode::Full && self.policy.clean_sequence {
+            self.epd.set_refresh_mode(RefreshMode::Full);
+            for black in [false, true, false] {
+                self.epd.display_solid(spi, black).await?;
+                self.refresh_count += 1;
+            }
+        }
+        // 驱动不支持的方式按完整刷新计数
+        let mode = self.epd.set_refresh_mode(mode);
+        self.epd.display_buffer(spi, buffer).await?;
+        self.refresh_count += 1;
+        self.last_refresh_ms = Some(now_ms);
+
+        match mode {
+            RefreshMode::Full => {
+                self.fast_refreshes = 0;
+                self.last_full_ms = Some(now_ms);
+            }
+            RefreshMode::FastMono => self.fast_refreshes += 1,
+        }
+        Ok(mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use core::convert::Infallible;
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+    use embedded_hal::spi::{ErrorType, Operation};
+    use std::vec::Vec;
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    struct NoSpi;
+
+    impl ErrorType for NoSpi {
+        type Error = Infallible;
+    }
+
+    impl SpiDevice for NoSpi {
+        async fn transaction(&mut self, _: &mut [Operation<'_, u8>]) -> Result<(), Infallible> {
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Event {
+        Buffer(RefreshMode),
+        Solid(bool),
+    }
+
+    /// 记录刷新顺序的驱动，`fast` 为是否支持快速刷新
+    struct FakeEpd {
+        fast: bool,
+        mode: RefreshMode,
+        events: Vec<Event>,
+    }
+
+    impl FakeEpd {
+        fn new(fast: bool) -> Self {
+            FakeEpd {
+                fast,
+                mode: RefreshMode::Full,
+                events: Vec::new(),
+            }
+        }
+    }
+
+    impl RefreshControl<NoSpi> for FakeEpd {
+        fn set_refresh_mode(&mut self, mode: RefreshMode) -> RefreshMode {
+            self.mode = if self.fast { mode } else { RefreshMode::Full };
+            self.mode
+        }
+
+        async fn display_buffer(&mut self, _: &mut NoSpi, _: &[u8]) -> Result<(), Infallible> {
+            self.events.push(Event::Buffer(self.mode));
+            Ok(())
+        }
+
+        async fn display_solid(&mut self, _: &mut NoSpi, black: bool) -> Result<(), Infallible> {
+            self.events.push(Event::Solid(black));
+            Ok(())
+        }
+    }
+
+    fn refresh(
+        manager: &mut RefreshManager<FakeEpd>,
+        mode: RefreshMode,
+        now_ms: u64,
+    ) -> Option<RefreshMode> {
+        block_on(manager.update_and_display_frame(&mut NoSpi, &[], mode, now_ms)).unwrap()
+    }
+
+    fn policy(max_fast_refreshes: u32, max_interval_ms: u64) -> RefreshPolicy {
+        RefreshPolicy {
+            max_fast_refreshes,
+            max_interval_ms,
+            ..RefreshPolicy::default()
+        }
+    }
+
+    #[test]
+    fn full_refresh_after_max_fast_refreshes() {
+        let mut manager = RefreshManager::new(FakeEpd::new(true), policy(2, u64::MAX));
+        let modes: Vec<_> = (0..7)
+            .map(|i| refresh(&mut manager, RefreshMode::FastMono, i))
+            .collect();
+
+        use RefreshMode::{FastMono, Full};
+        assert_eq!(
+            modes,
+            [Full, FastMono, FastMono, Full, FastMono, FastMono, Full].map(Some)
+        );
+        assert_eq!(manager.refresh_count(), 7);
+        assert_eq!(manager.fast_refreshes(), 0);
+    }
+
+    #[test]
+    fn full_refresh_after_max_interval() {
+        let mut manager = RefreshManager::new(FakeEpd::new(true), policy(100, 1_000));
+        assert_eq!(
+            refresh(&mut manager, RefreshMode::FastMono, 0),
+            Some(RefreshMode::Full)
+        );
+        assert_eq!(
+            refresh(&mut manager, RefreshMode::FastMono, 999),
+            Some(RefreshMode::FastMono)
+        );
+        assert!(manager.needs_full_refresh(1_000));
+        assert_eq!(
+            refresh(&mut manager, RefreshMode::FastMono, 1_000),
+            Some(RefreshMode::Full)
+        );
+    }
+
+    #[test]
+    fn unsupported_mode_counts_as_full() {
+        let mut manager = RefreshManager::new(FakeEpd::new(false), policy(2, u64::MAX));
+        for now_ms in 0..3 {
+            assert_eq!(
+                refresh(&mut manager, RefreshMode::FastMono, now_ms),
+                Some(RefreshMode::Full)
+            );
+        }
+        assert_eq!(manager.fast_refreshes(), 0);
+    }
+
+    #[test]
+    fn clean_sequence_precedes_forced_full_refresh() {
+        let policy = RefreshPolicy {
+            clean_sequence: true,
+            ..policy(1, u64::MAX)
+        };
+        let mut manager = RefreshManager::new(FakeEpd::new(true), policy);
+        refresh(&mut manager, RefreshMode::FastMono, 0);
+        refresh(&mut manager, RefreshMode::FastMono, 1);
+
+        use Event::{Buffer, Solid};
+        assert_eq!(
+            manager.epd().events,
+            [
+                Solid(false),
+                Solid(true),
+                Solid(false),
+                Buffer(RefreshMode::Full),
+                Buffer(RefreshMode::FastMono),
+            ]
+        );
+        assert_eq!(manager.refresh_count(), 5);
+    }
+
+    #[test]
+    fn refreshes_within_min_interval_are_merged() {
+        let policy = RefreshPolicy {
+            min_interval_ms: 100,
+            ..policy(10, u64::MAX)
+        };
+        let mut manager = RefreshManager::new(FakeEpd::new(true), policy);
+        assert_eq!(
+            refresh(&mut manager, RefreshMode::Full, 0),
+            Some(RefreshMode::Full)
+        );
+        assert_eq!(refresh(&mut manager, RefreshMode::FastMono, 10), None);
+        assert_eq!(refresh(&mut manager, RefreshMode::Full, 50), None);
+        assert_eq!(refresh(&mut manager, RefreshMode::FastMono, 70), None);
+        assert!(manager.is_pending());
+        assert_eq!(manager.next_refresh_ms(), Some(100));
+
+        let mut flush = |now_ms| block_on(manager.flush(&mut NoSpi, &[], now_ms)).unwrap();
+        assert_eq!(flush(99), None);
+        // 合并的请求中有完整刷新
+        assert_eq!(flush(100), Some(RefreshMode::Full));
+        assert_eq!(flush(500), None);
+        assert_eq!(manager.refresh_count(), 2);
+    }
+
+    #[test]
+    fn zero_min_interval_never_defers() {
+        let mut manager = RefreshManager::new(FakeEpd::new(true), RefreshPolicy::default());
+        for now_ms in [0, 0, 0] {
+            assert!(refresh(&mut manager, RefreshMode::FastMono, now_ms).is_some());
+        }
+        assert!(!manager.is_pending());
+    }
+}
@@ -0,0 +1,269 @@
+//! 游程编码压缩的帧格式
+//!
+//! 四色帧数据大部分为白色（`0x55`），游程编码即可大幅压缩。
+//! [`encode`] 可在主机或设备上使用，[`Decoder`] 按字节流式解压，
+//! 可直接交给驱动的 `update_frame_from_iter` 传输，无需完整的帧缓冲区：
+//!
+//! ```ignore
+//! let frame = rle::Decoder::new(COMPRESSED)?;
+//! epd.update_frame_from_iter(&mut spi, frame).await?;
+//! ```
+//!
+//! # 格式
+//!
+//! | 偏移 | 长度 | 内容                      |
+//! |------|------|---------------------------|
+//! | 0    | 4    | 魔数 `b"ERLE"`             |
+//! | 4    | 4    | 解压后长度（u32 小端）      |
+//! | 8    | ..   | 数据块                    |
+//!
+//! 每个数据块以控制字节 `c` 开头：
+//! - `0x00..=0x7F`：原样数据，随后 `c + 1` 个字节原样输出
+//! - `0x80..=0xFE`：短游程，随后 1 个字节重复 `c - 0x80 + 3` 次
+//! - `0xFF`：长游程，随后为重复次数（u16 小端）和 1 个字节
+
+/// 文件魔数
+pub const MAGIC: [u8; 4] = *b"ERLE";
+
+/// 文件头长度
+pub const HEADER_LEN: usize = 8;
+
+const MAX_LITERAL: usize = 128;
+const MIN_RUN: usize = 3;
+const MAX_SHORT_RUN: usize = 0xFE - 0x80 + MIN_RUN;
+const MAX_LONG_RUN: usize = u16::MAX as usize;
+const LONG_RUN: u8 = 0xFF;
+
+/// 解压错误
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// 魔数不匹配
+    BadMagic,
+    /// 数据在数据块中间结束
+    Truncated,
+    /// 解压后长度与文件头不符
+    LengthMismatch,
+}
+
+/// 压缩数据，通过 `out` 依次输出压缩结果（包括文件头）
+///
+/// 输入长度不能超过 `u32::MAX`
+pub fn encode(data: &[u8], mut out: impl FnMut(&[u8])) {
+    out(&MAGIC);
+    out(&(data.len() as u32).to_le_bytes());
+
+    let mut literal_start = 0;
+    let mut i = 0;
+    while i < data.len() {
+        let value = data[i];
+        let run = data[i..]
+            .iter()
+            .take(MAX_LONG_RUN)
+            .take_while(|&&byte| byte == value)
+            .count();
+
+        if run >= MIN_RUN {
+            flush_literal(&data[literal_start..i], &mut out);
+            if run <= MAX_SHORT_RUN {
+                out(&[(run - MIN_RUN) as u8 + 0x80, value]);
+            } else {
+                let [low, high] = (run as u16).to_le_bytes();
+                out(&[LONG_RUN, low, high, value]);
+            }
+            i += run;
+            literal_start = i;
+        } else {
+            i += 1;
+            if i - literal_start == MAX_LITERAL {
+                flush_literal(&data[literal_start..i], &mut out);
+                literal_start = i;
+            }
+        }
+    }
+    flush_literal(&data[literal_start..], &mut out);
+}
+
+fn flush_literal(literal: &[u8], out: &mut impl FnMut(&[u8])) {
+    if !literal.is_empty() {
+        out(&[(literal.len() - 1) as u8]);
+        out(literal);
+    }
+}
+
+/// 当前数据块
+#[derive(Clone, Copy, Debug)]
+enum Block {
+    /// 剩余原样字节数
+    Literal(usize),
+    /// 重复的字节及剩余次数
+    Run(u8, usize),
+}
+
+/// 流式解压器，逐字节产生解压后的数据
+///
+/// 创建时会完整校验压缩数据，因此迭代过程中不会出错
+#[derive(Clone, Debug)]
+pub struct Decoder<'a> {
+    /// 尚未解析的数据块
+    input: &'a [u8],
+    block: Block,
+    remaining: usize,
+}
+
+impl<'a> Decoder<'a> {
+    /// 校验压缩数据并创建解压器
+    pub fn new(data: &'a [u8]) -> Result<Self, DecodeError> {
+        if data.len() < HEADER_LEN || data[..4] != MAGIC {
+            return Err(DecodeError::BadMagic);
+        }
+        let len = u32::from_le_bytes([data[4], data[5], data[6], data[7]]) as usize;
+        let input = &data[HEADER_LEN..];
+
+        let mut decoded = 0;
+        let mut rest = input;
+        while let Some((count, next)) = skip_block(rest)? {
+            decoded += count;
+            rest = next;
+        }
+        if decoded != len {
+            return Err(DecodeError::LengthMismatch);
+        }
+
+        Ok(Decoder {
+            input,
+            block: Block::Literal(0),
+            remaining: len,
+        })
+    }
+}
+
+/// 跳过一个数据块，返回其解压后的字节数及其后的数据
+fn skip_block(input: &[u8]) -> Result<Option<(usize, &[u8])>, DecodeError> {
+    let Some((&control, rest)) = input.split_first() else {
+        return Ok(None);
+    };
+    let (count, consumed) = match control {
+        0x00..=0x7F => (control as usize + 1, control as usize + 1),
+        LONG_RUN => match rest {
+            [low, high, ..] => (u16::from_le_bytes([*low, *high]) as usize, 3),
+            _ => return Err(DecodeError::Truncated),
+        },
+        _ => ((control - 0x80) as usize + MIN_RUN, 1),
+    };
+    if rest.len() < consumed {
+        return Err(DecodeError::Truncated);
+    }
+    Ok(Some((count, &rest[consumed..])))
+}
+
+impl Iterator for Decoder<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        loop {
+            match &mut self.block {
+                Block::Literal(count) if *count > 0 => {
+                    *count -= 1;
+                    let (&byte, rest) = self.input.split_first()?;
+                    self.input = rest;
+                    self.remaining -= 1;
+                    return Some(byte);
+                }
+                Block::Run(value, count) if *count > 0 => {
+                    *count -= 1;
+                    self.remaining -= 1;
+                    return Some(*value);
+                }
+                _ => {}
+            }
+
+            let (&control, rest) = self.input.split_first()?;
+            self.block = match control {
+                0x00..=0x7F => {
+                    self.input = rest;
+                    Block::Literal(control as usize + 1)
+                }
+                LONG_RUN => {
+                    self.input = &rest[3..];
+                    Block::Run(rest[2], u16::from_le_bytes([rest[0], rest[1]]) as usize)
+                }
+                _ => {
+                    self.input = &rest[1..];
+                    Block::Run(rest[0], (control - 0x80) as usize + MIN_RUN)
+                }
+            };
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for Decoder<'_> {}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::vec::Vec;
+
+    fn compress(data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        encode(data, |chunk| out.extend_from_slice(chunk));
+        out
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut data = Vec::new();
+        data.extend((0..300).map(|i| i as u8));
+        data.extend([0x55; 2]);
+        data.extend([0x00; MAX_SHORT_RUN]);
+        data.extend([0xAA; MAX_SHORT_RUN + 1]);
+        data.extend([0x55; MAX_LONG_RUN + 10]);
+        data.push(0x01);
+
+        for input in [&data[..], &[], &[0x55; 3]] {
+            let compressed = compress(input);
+            let decoder = Decoder::new(&compressed).unwrap();
+            assert_eq!(decoder.len(), input.len());
+            assert_eq!(decoder.collect::<Vec<_>>(), input);
+        }
+    }
+
+    #[test]
+    fn white_frame_compresses() {
+        let compressed = compress(&[0x55; 96_000]);
+        assert!(compressed.len() < 64);
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert_eq!(Decoder::new(b"ERL").unwrap_err(), DecodeError::BadMagic);
+        assert_eq!(
+            Decoder::new(b"XRLE\0\0\0\0").unwrap_err(),
+            DecodeError::BadMagic
+        );
+
+        let compressed = compress(&[1, 2, 3, 4, 5, 5, 5, 5]);
+        for len in HEADER_LEN + 1..compressed.len() {
+            assert!(Decoder::new(&compressed[..len]).is_err());
+        }
+
+        let mut wrong_len = compressed.clone();
+        wrong_len[4] += 1;
+        assert_eq!(
+            Decoder::new(&wrong_len).unwrap_err(),
+            DecodeError::LengthMismatch
+        );
+
+        let mut truncated_run = compressed[..HEADER_LEN].to_vec();
+        truncated_run.extend([LONG_RUN, 0x10]);
+        assert_eq!(
+            Decoder::new(&truncated_run).unwrap_err(),
+            DecodeError::Truncated
+        );
+    }
+}
@@ -77,6 +77,17 @@ where
     /// 此函数会等待设备空闲
     async fn display_frame(&mut self, spi: &mut SPI) -> Result<(), SPI::Error>;
 
+    /// 发送刷新命令，等到 BUSY 引脚进入忙状态后返回，不等待刷新完成
+    ///
+    /// 刷新期间可以处理其他任务，之后用 [`is_busy`](WaveshareDisplay::is_busy) 查询，
+    /// 或用 [`wait_until_idle`](WaveshareDisplay::wait_until_idle) 等待 BUSY 引脚变化。
+    /// 返回时 BUSY 已拉起，随后立即查询不会误读为空闲
+    ///
+    /// 默认实现调用 [`display_frame`](WaveshareDisplay::display_frame)，返回时刷新已完成
+    async fn start_refresh(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        self.display_frame(spi).await
+    }
+
     /// 合并更新和显示操作（跳过中间的忙检查）
     async fn update_and_display_frame(
         &mut self,
@@ -84,13 +95,20 @@ where
         buffer: &[u8],
     ) -> Result<(), SPI::Error>;
 
-    /// 用背景颜色清空 EPD 帧缓冲区
+    /// 用背景颜色填充 EPD 的 SRAM，不触发刷新
     ///
-    /// 背景颜色为COLOR::default()
+    /// 背景颜色为COLOR::default()，需要再调用 [`display_frame`](WaveshareDisplay::display_frame) 显示
     async fn clear_frame(&mut self, spi: &mut SPI) -> Result<(), SPI::Error>;
 
     /// 等待显示停止处理数据
     ///
     /// 调用此函数可确保帧显示完成
     async fn wait_until_idle(&mut self) -> Result<(), SPI::Error>;
+
+    /// BUSY 引脚是否指示设备忙
+    ///
+    /// 默认实现始终返回 `false`，与 `start_refresh` 的默认实现配套
+    fn is_busy(&mut self) -> bool {
+        false
+    }
 }
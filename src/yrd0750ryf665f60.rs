@@ -8,14 +8,19 @@ use embedded_hal_async::{digital::Wait, spi::SpiDevice};
 
 use crate::color::QuadColor;
 use crate::interface::DisplayInterface;
+use crate::refresh::{RefreshControl, RefreshMode};
 use crate::traits::{InternalWiAdditions, WaveshareDisplay};
 
+#[cfg(feature = "graphics")]
 use crate::buffer_len;
 use crate::traits;
 
 #[cfg(feature = "simulator")]
 use embedded_graphics_core::prelude::*;
 
+#[cfg(feature = "simulator")]
+extern crate std;
+
 #[cfg(feature = "simulator")]
 use embedded_graphics_simulator::{OutputSettingsBuilder, SimulatorDisplay, Window};
 
@@ -28,11 +33,20 @@ pub type Display7in5 = crate::graphics::Display<
     QuadColor,
 >;
 
+/// 面板编号，用于校验 `Display::to_bytes` 导出的缓冲区文件
+pub const PANEL_ID: u16 = 0x0750;
+
 /// 显示宽度
 pub const WIDTH: u32 = 800;
 /// 显示高度
 pub const HEIGHT: u32 = 480;
 
+/// 每行像素数据的字节数
+const ROW_BYTES: usize = WIDTH as usize / 4;
+
+/// 整帧数据的字节数
+const FRAME_BYTES: usize = ROW_BYTES * HEIGHT as usize;
+
 const IS_BUSY_LOW: bool = true;
 
 /// EPD 命令
@@ -113,15 +127,53 @@ impl traits::Command for Command {
 
 /// Epd7in5 (yrd0750ryf665f60) 驱动
 pub struct Epd7in5<SPI, BUSY, DC, RST, DELAY> {
-    interface: DisplayInterface<SPI, BUSY, DC, RST>,
+    interface: DisplayInterface<SPI, BUSY, DC, RST, DELAY>,
     _color: PhantomData<QuadColor>,
-    _delay: PhantomData<DELAY>,
     #[cfg(feature = "simulator")]
     simulator_window: Option<core::cell::RefCell<Window>>,
     #[cfg(feature = "simulator")]
     simulator_display: SimulatorDisplay<QuadColor>,
 }
 
+impl<SPI, BUSY, DC, RST, DELAY> Epd7in5<SPI, BUSY, DC, RST, DELAY>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin + Wait,
+    DC: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+{
+    impl_power_methods!();
+
+    impl_energy_methods!();
+
+    impl_lvd_methods!();
+
+    impl_frame_methods!(QuadColor, Command::DataStartTransmission1);
+
+    /// 用指定颜色填充整帧 SRAM，不触发刷新
+    ///
+    /// 需要填充图案时使用 [`update_frame_pattern`](Self::update_frame_pattern)
+    pub async fn clear_frame_with(
+        &mut self,
+        spi: &mut SPI,
+        color: QuadColor,
+    ) -> Result<(), SPI::Error> {
+        self.update_frame_pattern(spi, &[color.bits() * 0x55]).await
+    }
+
+    impl_color_methods!(
+        QuadColor,
+        QuadColor::White,
+        [
+            QuadColor::Black,
+            QuadColor::White,
+            QuadColor::Red,
+            QuadColor::Yellow,
+        ]
+    );
+}
+
 impl<SPI, BUSY, DC, RST, DELAY> InternalWiAdditions<SPI, BUSY, DC, RST, DELAY>
     for Epd7in5<SPI, BUSY, DC, RST, DELAY>
 where
@@ -155,10 +207,8 @@ where
         self.interface
             .cmd_with_data(spi, Command::PllControl, &[0x08])
             .await?;
-        self.interface.cmd(spi, Command::PowerOn).await?;
-        self.wait_until_idle().await?;
-
-        Ok(())
+        self.set_lvd_threshold(spi, self.lvd_threshold()).await?;
+        self.power_on(spi).await
     }
 }
 
@@ -180,12 +230,12 @@ where
         rst: RST,
         _delay: &mut DELAY,
     ) -> Result<Self, SPI::Error> {
-        let interface = DisplayInterface::new(busy, dc, rst);
+        let mut interface = DisplayInterface::new(busy, dc, rst);
+        interface.set_power_off_data(&[0x00]);
 
         let epd = Epd7in5 {
             interface,
             _color: PhantomData,
-            _delay: PhantomData,
             #[cfg(feature = "simulator")]
             simulator_window: None,
             #[cfg(feature = "simulator")]
@@ -203,59 +253,34 @@ where
     }
 
     async fn sleep(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
-        self.wait_until_idle().await?;
-        self.interface
-            .cmd_with_data(spi, Command::PowerOff, &[0x00])
-            .await?;
-        self.wait_until_idle().await?;
-        self.interface
-            .cmd_with_data(spi, Command::DeepSleep, &[0xA5])
-            .await?;
-        Ok(())
+        self.interface.deep_sleep(spi, IS_BUSY_LOW).await
     }
 
     #[cfg(feature = "simulator")]
-    async fn update_frame(&mut self, _spi: &mut SPI, buffer: &[u8]) -> Result<(), SPI::Error> {
-        use crate::color::ColorType;
-
-        let color_iter = buffer.iter().flat_map(|byte| {
-            [0, 2, 4, 6].iter().map(move |&shift| {
-                let pixel_bits = (*byte >> shift) & 0x03;
-                QuadColor::from_bits(pixel_bits)
-            })
-        });
-
-        let pixels = color_iter.enumerate().map(|(i, color)| {
-            let x = (i % WIDTH as usize) as i32;
-            let y = (i / WIDTH as usize) as i32;
-            Pixel(Point::new(x, y), color)
-        });
-
-        self.simulator_display
-            .draw_iter(pixels)
-            .expect("Failed to draw frame to EPD simulator");
-
-        Ok(())
+    async fn update_frame(&mut self, spi: &mut SPI, buffer: &[u8]) -> Result<(), SPI::Error> {
+        self.update_frame_from_iter(spi, buffer.iter().copied())
+            .await
     }
 
     #[cfg(not(feature = "simulator"))]
     async fn update_frame(&mut self, spi: &mut SPI, buffer: &[u8]) -> Result<(), SPI::Error> {
         self.wait_until_idle().await?;
         self.interface
-            .cmd_with_data(
-                spi,
-                Command::DataStartTransmission1,
-                &buffer[..(WIDTH as usize / 4 * HEIGHT as usize)],
-            )
+            .cmd_with_data(spi, Command::DataStartTransmission1, &buffer[..FRAME_BYTES])
             .await?;
         Ok(())
     }
 
     #[cfg(feature = "simulator")]
-    async fn display_frame(&mut self, _spi: &mut SPI) -> Result<(), SPI::Error> {
+    async fn display_frame(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        self.start_refresh(spi).await
+    }
+
+    #[cfg(feature = "simulator")]
+    async fn start_refresh(&mut self, _spi: &mut SPI) -> Result<(), SPI::Error> {
         if self.simulator_window.is_none() {
             self.simulator_window = Some(core::cell::RefCell::new(Window::new(
-                &format!("EPD Simulator {}x{}", WIDTH, HEIGHT),
+                &std::format!("EPD Simulator {}x{}", WIDTH, HEIGHT),
                 &OutputSettingsBuilder::new().scale(1).build(),
             )));
         }
@@ -267,12 +292,15 @@ where
 
     #[cfg(not(feature = "simulator"))]
     async fn display_frame(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
+        self.start_refresh(spi).await?;
+        self.interface.finish_refresh(spi, IS_BUSY_LOW).await
+    }
+
+    #[cfg(not(feature = "simulator"))]
+    async fn start_refresh(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
         self.interface
-            .cmd_with_data(spi, Command::DisplayRefresh, &[0x00])
-            .await?;
-        // delay.delay_us(500);
-        self.wait_until_idle().await?;
-        Ok(())
+            .start_refresh(spi, IS_BUSY_LOW, &[0x00])
+            .await
     }
 
     async fn update_and_display_frame(
@@ -281,26 +309,12 @@ where
         buffer: &[u8],
     ) -> Result<(), SPI::Error> {
         self.update_frame(spi, buffer).await?;
-        self.interface.cmd(spi, Command::PowerOn).await?;
         self.display_frame(spi).await?;
         Ok(())
     }
 
     async fn clear_frame(&mut self, spi: &mut SPI) -> Result<(), SPI::Error> {
-        self.wait_until_idle().await?;
-
-        self.interface
-            .cmd(spi, Command::DataStartTransmission1)
-            .await?;
-        self.interface
-            .data_x_times(spi, QuadColor::default_color_byte(), WIDTH * HEIGHT / 4)
-            .await?;
-
-        self.interface.cmd(spi, Command::DataStop).await?;
-
-        self.interface.cmd(spi, Command::DisplayRefresh).await?;
-
-        Ok(())
+        self.clear_frame_with(spi, QuadColor::White).await
     }
 
     fn width(&self) -> u32 {
@@ -315,4 +329,35 @@ where
         self.interface.wait_until_idle(IS_BUSY_LOW).await;
         Ok(())
     }
+
+    fn is_busy(&mut self) -> bool {
+        self.interface.is_busy(IS_BUSY_LOW)
+    }
+}
+
+impl<SPI, BUSY, DC, RST, DELAY> RefreshControl<SPI> for Epd7in5<SPI, BUSY, DC, RST, DELAY>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin + Wait,
+    DC: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+{
+    /// 仅支持完整刷新
+    fn set_refresh_mode(&mut self, _mode: RefreshMode) -> RefreshMode {
+        RefreshMode::Full
+    }
+
+    async fn display_buffer(&mut self, spi: &mut SPI, buffer: &[u8]) -> Result<(), SPI::Error> {
+        self.update_and_display_frame(spi, buffer).await
+    }
+
+    async fn display_solid(&mut self, spi: &mut SPI, black: bool) -> Result<(), SPI::Error> {
+        let color = if black {
+            QuadColor::Black
+        } else {
+            QuadColor::White
+        };
+        self.display_color(spi, color).await
+    }
 }